@@ -1,16 +1,20 @@
 use cosmwasm_std::{
-    to_json_binary, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
-    SubMsg, SubMsgResult, WasmMsg,
+    to_json_binary, Binary, CosmosMsg, Decimal, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdResult, SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
 
 use snb_base::{
     error::ContractError,
     nft_minter::{
         state::{
-            COLLECTIONS, CONFIG, SAVE_CW721_ADDRESS_REPLY, TRANSFER_ADMIN_STATE,
-            TRANSFER_ADMIN_TIMEOUT,
+            COLLECTIONS, CONFIG, MINTERS, PENDING_MINTER, SAVE_CW721_ADDRESS_REPLY,
+            TRANSFER_ADMIN_STATE, TRANSFER_ADMIN_TIMEOUT,
+        },
+        types::{
+            CollectionFlavor, CollectionInfo, Config, Cw2981Metadata, Cw721OwnershipResponse,
+            Metadata, RoyaltyInfo, Sg721CollectionInfo, Sg721CollectionInfoResponse,
+            Sg721InstantiateMsg, Sg721QueryMsg, TokenMetadata, TransferAdminState,
         },
-        types::{Config, TransferAdminState},
     },
     utils::{check_authorization, check_funds, unwrap_field, AuthType, FundsType},
 };
@@ -48,12 +52,16 @@ pub fn try_accept_admin_role(
     Ok(Response::new().add_attribute("action", "try_accept_admin_role"))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_update_config(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     admin: Option<String>,
     wrapper: Option<String>,
+    cw721_metadata_onchain_code_id: Option<u64>,
+    sg721_code_id: Option<u64>,
+    cw2981_code_id: Option<u64>,
 ) -> Result<Response, ContractError> {
     let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
     let mut config = CONFIG.load(deps.storage)?;
@@ -85,6 +93,21 @@ pub fn try_update_config(
         is_config_updated = true;
     }
 
+    if let Some(x) = cw721_metadata_onchain_code_id {
+        config.cw721_metadata_onchain_code_id = Some(x);
+        is_config_updated = true;
+    }
+
+    if let Some(x) = sg721_code_id {
+        config.sg721_code_id = Some(x);
+        is_config_updated = true;
+    }
+
+    if let Some(x) = cw2981_code_id {
+        config.cw2981_code_id = Some(x);
+        is_config_updated = true;
+    }
+
     // don't allow empty messages
     if !is_config_updated {
         Err(ContractError::NoParameters)?;
@@ -95,11 +118,20 @@ pub fn try_update_config(
     Ok(Response::new().add_attribute("action", "try_update_config"))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_create_collection(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     name: String,
+    symbol: Option<String>,
+    description: Option<String>,
+    image: Option<String>,
+    external_link: Option<String>,
+    label: Option<String>,
+    flavor: Option<CollectionFlavor>,
+    royalty_info: Option<RoyaltyInfo>,
+    minter: Option<String>,
 ) -> Result<Response, ContractError> {
     let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
     let config = CONFIG.load(deps.storage)?;
@@ -108,7 +140,9 @@ pub fn try_create_collection(
         &sender_address,
         &config.admin,
         &config.wrapper, // placeholder
-        AuthType::Admin,
+        AuthType::AdminOrSpecified {
+            allowlist: vec![Some(config.transceiver_hub.to_owned())],
+        },
     )?;
 
     let collection_list = COLLECTIONS
@@ -117,25 +151,91 @@ pub fn try_create_collection(
 
     if collection_list
         .iter()
-        .any(|(_, current_name)| current_name == &name)
+        .any(|(_, collection_info)| collection_info.name == name)
     {
         Err(ContractError::CollectionDuplication)?;
     }
 
+    if let Some(RoyaltyInfo {
+        payment_address,
+        share,
+    }) = &royalty_info
+    {
+        deps.api.addr_validate(payment_address)?;
+
+        if share > &Decimal::one() {
+            Err(ContractError::WrongRoyaltyShare)?;
+        }
+    }
+
+    let flavor = flavor.unwrap_or_default();
+    let code_id = config
+        .get_code_id(&flavor)
+        .ok_or(ContractError::CodeIdIsNotFound)?;
+    let collection_info = CollectionInfo {
+        label: label.unwrap_or(format!("Simple NFT Bridge collection: {}", name)),
+        name,
+        symbol: symbol.unwrap_or_default(),
+        description,
+        image,
+        external_link,
+        flavor,
+        royalty_info,
+    };
+
     // will be updated on reply
-    COLLECTIONS.save(deps.storage, nft_minter, &name)?;
+    COLLECTIONS.save(deps.storage, nft_minter, &collection_info)?;
+
+    // collections created by the transceiver hub are bridged ones
+    match minter {
+        Some(x) => PENDING_MINTER.save(deps.storage, &deps.api.addr_validate(&x)?)?,
+        None if sender_address == config.transceiver_hub => {
+            PENDING_MINTER.save(deps.storage, &sender_address)?
+        }
+        None => PENDING_MINTER.remove(deps.storage),
+    }
 
-    let cw721_msg = cw721_base::msg::InstantiateMsg {
-        name: name.clone(),
-        symbol: String::default(),
-        minter: nft_minter.to_string(),
+    let CollectionInfo {
+        name,
+        symbol,
+        description,
+        image,
+        external_link,
+        label,
+        flavor,
+        royalty_info,
+    } = collection_info;
+    let minter = nft_minter.to_string();
+
+    let cw721_msg = match flavor {
+        CollectionFlavor::Cw721Base
+        | CollectionFlavor::Cw721MetadataOnchain
+        | CollectionFlavor::Cw2981 => to_json_binary(&cw721_base::msg::InstantiateMsg {
+            name,
+            symbol,
+            minter,
+        })?,
+        CollectionFlavor::Sg721 => to_json_binary(&Sg721InstantiateMsg {
+            name,
+            symbol,
+            minter,
+            collection_info: Sg721CollectionInfo {
+                creator: config.admin.to_string(),
+                description: description.unwrap_or_default(),
+                image: unwrap_field(image, "image")?,
+                external_link,
+                explicit_content: None,
+                start_trading_time: None,
+                royalty_info,
+            },
+        })?,
     };
 
     let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Instantiate {
         admin: Some(config.admin.to_string()),
-        code_id: config.cw721_code_id,
-        label: format!("Simple NFT Bridge collection: {}", name),
-        msg: to_json_binary(&cw721_msg)?,
+        code_id,
+        label,
+        msg: cw721_msg,
         funds: vec![],
     });
 
@@ -171,42 +271,230 @@ pub fn save_cw721_address(
     )?
     .value;
 
-    let name = COLLECTIONS.load(deps.storage, nft_minter)?;
+    let cw721_address = &deps.api.addr_validate(cw721_address)?;
+    let collection_info = COLLECTIONS.load(deps.storage, nft_minter)?;
     COLLECTIONS.remove(deps.storage, nft_minter);
-    COLLECTIONS.save(deps.storage, &deps.api.addr_validate(cw721_address)?, &name)?;
+    COLLECTIONS.save(deps.storage, cw721_address, &collection_info)?;
+
+    if let Some(minter) = PENDING_MINTER.may_load(deps.storage)? {
+        PENDING_MINTER.remove(deps.storage);
+        MINTERS.save(deps.storage, (cw721_address, &minter), &true)?;
+    }
 
     Ok(Response::new().add_attribute("cw721_address", cw721_address))
 }
 
-pub fn try_mint(
+pub fn try_register_collection(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    label: Option<String>,
+    minter: Option<String>,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+    let nft_minter = &env.contract.address;
+    check_authorization(
+        &sender_address,
+        &config.admin,
+        &config.wrapper, // placeholder
+        AuthType::Admin,
+    )?;
+
+    let collection = &deps.api.addr_validate(&address)?;
+
+    if COLLECTIONS.has(deps.storage, collection) {
+        Err(ContractError::CollectionDuplication)?;
+    }
+
+    let minter = match minter {
+        Some(x) => Some(deps.api.addr_validate(&x)?),
+        None if sender_address == config.transceiver_hub => Some(sender_address),
+        None => None,
+    };
+
+    // cw721 v0.18+ stores minter as cw-ownable owner
+    let owner = deps
+        .querier
+        .query_wasm_smart::<cw721_base::msg::MinterResponse>(
+            collection,
+            &cw721_base::QueryMsg::<Empty>::Minter {},
+        )
+        .map(|x| x.minter)
+        .or_else(|_| {
+            deps.querier
+                .query_wasm_smart::<Cw721OwnershipResponse>(
+                    collection,
+                    &cw721_base::QueryMsg::<Empty>::Ownership {},
+                )
+                .map(|x| x.owner)
+        })?;
+
+    if owner.as_deref() != Some(nft_minter.as_str()) {
+        Err(ContractError::WrongCollectionMinter)?;
+    }
+
+    let cw721::ContractInfoResponse { name, symbol } = deps
+        .querier
+        .query_wasm_smart(collection, &cw721::Cw721QueryMsg::ContractInfo {})?;
+
+    if COLLECTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .iter()
+        .any(|(_, collection_info)| collection_info.name == name)
+    {
+        Err(ContractError::CollectionDuplication)?;
+    }
+
+    // collections of unknown code are treated as cw721-base
+    let code_id = deps.querier.query_wasm_contract_info(collection)?.code_id;
+    let flavor = [
+        CollectionFlavor::Cw721MetadataOnchain,
+        CollectionFlavor::Sg721,
+        CollectionFlavor::Cw2981,
+    ]
+    .into_iter()
+    .find(|x| config.get_code_id(x) == Some(code_id))
+    .unwrap_or_default();
+
+    // cw2981 has no collection royalty, it's stored per token
+    let royalty_info = deps
+        .querier
+        .query_wasm_smart::<Sg721CollectionInfoResponse>(
+            collection,
+            &Sg721QueryMsg::CollectionInfo {},
+        )
+        .ok()
+        .and_then(|x| x.royalty_info);
+
+    COLLECTIONS.save(
+        deps.storage,
+        collection,
+        &CollectionInfo {
+            label: label.unwrap_or_else(|| name.clone()),
+            name: name.clone(),
+            symbol,
+            description: None,
+            image: None,
+            external_link: None,
+            flavor,
+            royalty_info,
+        },
+    )?;
+
+    if let Some(x) = &minter {
+        MINTERS.save(deps.storage, (collection, x), &true)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "try_register_collection")
+        .add_attribute("collection", collection)
+        .add_attribute("name", name))
+}
+
+pub fn try_grant_minter(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     collection: String,
-    token_list: Vec<String>,
-    recipient: String,
+    minter: String,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+    check_authorization(
+        &sender_address,
+        &config.admin,
+        &config.wrapper, // placeholder
+        AuthType::Admin,
+    )?;
+
+    let collection = &deps.api.addr_validate(&collection)?;
+    let minter = &deps.api.addr_validate(&minter)?;
+
+    if !COLLECTIONS.has(deps.storage, collection) {
+        Err(ContractError::CollectionIsNotFound)?;
+    }
+
+    MINTERS.save(deps.storage, (collection, minter), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "try_grant_minter")
+        .add_attribute("collection", collection)
+        .add_attribute("minter", minter))
+}
+
+pub fn try_revoke_minter(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    minter: String,
 ) -> Result<Response, ContractError> {
     let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
     let config = CONFIG.load(deps.storage)?;
     check_authorization(
         &sender_address,
-        &config.transceiver_hub,
-        &config.wrapper,
-        AuthType::AdminOrWorker,
+        &config.admin,
+        &config.wrapper, // placeholder
+        AuthType::Admin,
     )?;
 
+    let collection = &deps.api.addr_validate(&collection)?;
+    let minter = &deps.api.addr_validate(&minter)?;
+
+    if !MINTERS.has(deps.storage, (collection, minter)) {
+        Err(ContractError::MinterIsNotFound)?;
+    }
+
+    MINTERS.remove(deps.storage, (collection, minter));
+
+    Ok(Response::new()
+        .add_attribute("action", "try_revoke_minter")
+        .add_attribute("collection", collection)
+        .add_attribute("minter", minter))
+}
+
+pub fn try_mint(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_list: Vec<String>,
+    recipient: String,
+    metadata_list: Option<Vec<TokenMetadata>>,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let collection_address = &deps.api.addr_validate(&collection)?;
+
+    if !MINTERS.has(deps.storage, (collection_address, &sender_address)) {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    let metadata_list = metadata_list.unwrap_or_default();
+    if !metadata_list.is_empty() && metadata_list.len() != token_list.len() {
+        Err(ContractError::WrongMetadataList)?;
+    }
+
+    // collections created outside of the minter are treated as cw721-base
+    let (flavor, royalty_info) = COLLECTIONS
+        .may_load(deps.storage, collection_address)?
+        .map(|x| (x.flavor, x.royalty_info))
+        .unwrap_or_default();
+
     let msg_list = token_list
         .into_iter()
-        .map(|token_id| {
+        .enumerate()
+        .map(|(i, token_id)| {
             Ok(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: collection.to_string(),
-                msg: to_json_binary(
-                    &cw721_base::ExecuteMsg::Mint::<Option<Empty>, Option<Empty>> {
-                        token_id,
-                        owner: recipient.clone(),
-                        token_uri: None,
-                        extension: None,
-                    },
+                msg: get_mint_msg(
+                    &flavor,
+                    token_id,
+                    &recipient,
+                    metadata_list.get(i).cloned().unwrap_or_default(),
+                    royalty_info.as_ref(),
                 )?,
                 funds: vec![],
             }))
@@ -226,13 +514,11 @@ pub fn try_burn(
     token_list: Vec<String>,
 ) -> Result<Response, ContractError> {
     let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
-    let config = CONFIG.load(deps.storage)?;
-    check_authorization(
-        &sender_address,
-        &config.transceiver_hub,
-        &config.wrapper,
-        AuthType::AdminOrWorker,
-    )?;
+    let collection_address = &deps.api.addr_validate(&collection)?;
+
+    if !MINTERS.has(deps.storage, (collection_address, &sender_address)) {
+        Err(ContractError::Unauthorized)?;
+    }
 
     let msg_list = token_list
         .into_iter()
@@ -251,3 +537,77 @@ pub fn try_burn(
         .add_messages(msg_list)
         .add_attribute("action", "try_burn"))
 }
+
+/// extension is serialized according to the collection flavor,
+/// token royalty takes precedence over the collection one
+fn get_mint_msg(
+    flavor: &CollectionFlavor,
+    token_id: String,
+    owner: &str,
+    metadata: TokenMetadata,
+    collection_royalty_info: Option<&RoyaltyInfo>,
+) -> StdResult<Binary> {
+    let TokenMetadata {
+        token_uri,
+        extension,
+        royalty_info,
+    } = metadata;
+    let owner = owner.to_string();
+
+    match flavor {
+        CollectionFlavor::Cw721Base => to_json_binary(&cw721_base::ExecuteMsg::Mint::<
+            Option<Empty>,
+            Option<Empty>,
+        > {
+            token_id,
+            owner,
+            token_uri,
+            extension: None,
+        }),
+        CollectionFlavor::Cw721MetadataOnchain => to_json_binary(&cw721_base::ExecuteMsg::Mint::<
+            Option<Metadata>,
+            Option<Empty>,
+        > {
+            token_id,
+            owner,
+            token_uri,
+            extension,
+        }),
+        CollectionFlavor::Sg721 => {
+            to_json_binary(&cw721_base::ExecuteMsg::Mint::<Metadata, Option<Empty>> {
+                token_id,
+                owner,
+                token_uri,
+                extension: extension.unwrap_or_default(),
+            })
+        }
+        CollectionFlavor::Cw2981 => {
+            // cw2981 accepts integer percentage only, share is rounded half up to the whole
+            // percent, royalty exceeding 100% is skipped
+            let (royalty_percentage, royalty_payment_address) = royalty_info
+                .as_ref()
+                .or(collection_royalty_info)
+                .filter(|x| x.share <= Decimal::one())
+                .map(|x| {
+                    (
+                        ((x.share * Uint128::new(1_000)).u128() as u64 + 5) / 10,
+                        x.payment_address.to_owned(),
+                    )
+                })
+                .unzip();
+
+            to_json_binary(
+                &cw721_base::ExecuteMsg::Mint::<Option<Cw2981Metadata>, Option<Empty>> {
+                    token_id,
+                    owner,
+                    token_uri,
+                    extension: Some(Cw2981Metadata {
+                        metadata: extension.unwrap_or_default(),
+                        royalty_percentage,
+                        royalty_payment_address,
+                    }),
+                },
+            )
+        }
+    }
+}
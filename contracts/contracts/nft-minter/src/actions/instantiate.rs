@@ -28,6 +28,9 @@ pub fn try_instantiate(
             admin: sender.to_owned(),
             transceiver_hub: deps.api.addr_validate(&msg.transceiver_hub)?,
             cw721_code_id: msg.cw721_code_id,
+            cw721_metadata_onchain_code_id: None,
+            sg721_code_id: None,
+            cw2981_code_id: None,
             wrapper: msg
                 .wrapper
                 .map(|x| deps.api.addr_validate(&x))
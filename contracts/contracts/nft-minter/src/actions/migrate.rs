@@ -1,11 +1,16 @@
-use cosmwasm_std::{DepsMut, Env, Response, Storage};
+use cosmwasm_std::{Addr, DepsMut, Env, Order, Response, StdResult, Storage};
 use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Map;
 
 use semver::Version;
 
 use snb_base::{
     error::ContractError,
-    nft_minter::{msg::MigrateMsg, state::CONTRACT_NAME},
+    nft_minter::{
+        msg::MigrateMsg,
+        state::{COLLECTIONS, CONFIG, CONTRACT_NAME, MINTERS},
+        types::{CollectionFlavor, CollectionInfo},
+    },
 };
 
 pub fn migrate_contract(
@@ -19,6 +24,80 @@ pub fn migrate_contract(
         set_contract_version(deps.storage, CONTRACT_NAME, version_new.to_string())?;
     }
 
+    // collections were stored as names, symbol is queried from cw721 contract
+    // already migrated collections can't be parsed as names and are skipped
+    let legacy_collections = Map::<&Addr, String>::new("collections")
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|x| x.ok())
+        .collect::<Vec<_>>();
+
+    for (address, name) in legacy_collections {
+        let symbol = deps
+            .querier
+            .query_wasm_smart::<cw721::ContractInfoResponse>(
+                &address,
+                &cw721::Cw721QueryMsg::ContractInfo {},
+            )
+            .map(|x| x.symbol)
+            .unwrap_or_default();
+
+        COLLECTIONS.save(
+            deps.storage,
+            &address,
+            &CollectionInfo {
+                label: format!("Simple NFT Bridge collection: {}", name),
+                name,
+                symbol,
+                description: None,
+                image: None,
+                external_link: None,
+                flavor: CollectionFlavor::Cw721Base,
+                royalty_info: None,
+            },
+        )?;
+    }
+
+    // collections without minters are assigned to the wrapper if they are wrapped ones,
+    // to the transceiver hub otherwise
+    let config = CONFIG.load(deps.storage)?;
+    let wrapped_collections = match &config.wrapper {
+        Some(wrapper) => deps
+            .querier
+            .query_wasm_smart::<Vec<snb_base::wrapper::types::Collection>>(
+                wrapper,
+                &snb_base::wrapper::msg::QueryMsg::CollectionList {},
+            )?,
+        None => vec![],
+    };
+
+    let collection_list = COLLECTIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for collection in collection_list {
+        if MINTERS
+            .prefix(&collection)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+        {
+            continue;
+        }
+
+        let minter = match &config.wrapper {
+            Some(wrapper)
+                if wrapped_collections
+                    .iter()
+                    .any(|x| x.collection_out == collection) =>
+            {
+                wrapper
+            }
+            _ => &config.transceiver_hub,
+        };
+
+        MINTERS.save(deps.storage, (&collection, minter), &true)?;
+    }
+
     Ok(Response::new())
 }
 
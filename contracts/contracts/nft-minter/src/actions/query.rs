@@ -2,15 +2,15 @@ use cosmwasm_std::{Addr, Deps, Env, Order, StdResult};
 
 use cw_storage_plus::Bound;
 use snb_base::nft_minter::{
-    state::{COLLECTIONS, CONFIG},
-    types::Config,
+    state::{COLLECTIONS, CONFIG, MINTERS},
+    types::{CollectionInfo, Config},
 };
 
 pub fn query_config(deps: Deps, _env: Env) -> StdResult<Config> {
     CONFIG.load(deps.storage)
 }
 
-pub fn query_collection(deps: Deps, _env: Env, address: String) -> StdResult<String> {
+pub fn query_collection(deps: Deps, _env: Env, address: String) -> StdResult<CollectionInfo> {
     COLLECTIONS.load(deps.storage, &deps.api.addr_validate(&address)?)
 }
 
@@ -19,7 +19,7 @@ pub fn query_collection_list(
     _env: Env,
     amount: u32,
     start_after: Option<String>,
-) -> StdResult<Vec<(Addr, String)>> {
+) -> StdResult<Vec<(Addr, CollectionInfo)>> {
     let binding;
     let start_bound = match start_after {
         Some(addr) => {
@@ -34,3 +34,10 @@ pub fn query_collection_list(
         .take(amount as usize)
         .collect::<StdResult<_>>()
 }
+
+pub fn query_minters(deps: Deps, _env: Env, collection: String) -> StdResult<Vec<Addr>> {
+    MINTERS
+        .prefix(&deps.api.addr_validate(&collection)?)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
@@ -38,17 +38,76 @@ pub fn execute(
     match msg {
         ExecuteMsg::AcceptAdminRole {} => e::try_accept_admin_role(deps, env, info),
 
-        ExecuteMsg::UpdateConfig { admin, wrapper } => {
-            e::try_update_config(deps, env, info, admin, wrapper)
+        ExecuteMsg::UpdateConfig {
+            admin,
+            wrapper,
+            cw721_metadata_onchain_code_id,
+            sg721_code_id,
+            cw2981_code_id,
+        } => e::try_update_config(
+            deps,
+            env,
+            info,
+            admin,
+            wrapper,
+            cw721_metadata_onchain_code_id,
+            sg721_code_id,
+            cw2981_code_id,
+        ),
+
+        ExecuteMsg::CreateCollection {
+            name,
+            symbol,
+            description,
+            image,
+            external_link,
+            label,
+            flavor,
+            royalty_info,
+            minter,
+        } => e::try_create_collection(
+            deps,
+            env,
+            info,
+            name,
+            symbol,
+            description,
+            image,
+            external_link,
+            label,
+            flavor,
+            royalty_info,
+            minter,
+        ),
+
+        ExecuteMsg::RegisterCollection {
+            address,
+            label,
+            minter,
+        } => e::try_register_collection(deps, env, info, address, label, minter),
+
+        ExecuteMsg::GrantMinter { collection, minter } => {
+            e::try_grant_minter(deps, env, info, collection, minter)
         }
 
-        ExecuteMsg::CreateCollection { name } => e::try_create_collection(deps, env, info, name),
+        ExecuteMsg::RevokeMinter { collection, minter } => {
+            e::try_revoke_minter(deps, env, info, collection, minter)
+        }
 
         ExecuteMsg::Mint {
             collection,
             token_list,
             recipient,
-        } => e::try_mint(deps, env, info, collection, token_list, recipient),
+            metadata_list,
+        } => e::try_mint(
+            deps,
+            env,
+            info,
+            collection,
+            token_list,
+            recipient,
+            metadata_list,
+        ),
 
         ExecuteMsg::Burn {
             collection,
@@ -71,6 +130,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             amount,
             start_after,
         } => to_json_binary(&q::query_collection_list(deps, env, amount, start_after)?),
+
+        QueryMsg::Minters { collection } => {
+            to_json_binary(&q::query_minters(deps, env, collection)?)
+        }
     }
 }
 
@@ -1,28 +1,45 @@
 use cosmwasm_std::{
-    to_json_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, Timestamp, Uint128,
+    coin, coins, to_json_binary, to_json_string, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env,
+    IbcMsg, IbcTimeout, MessageInfo, Response, StdResult, SubMsg, SubMsgResult, Timestamp, Uint128,
     WasmMsg,
 };
 
-use encryption_helper::serde::{decrypt_deserialize, serialize_encrypt};
+use encryption_helper::serde::decrypt_deserialize;
 
 use snb_base::{
     converters::get_addr_by_prefix,
     error::ContractError,
-    private_communication::types::{EncryptedResponse, Hash},
+    nft_minter::types::{CollectionFlavor, TokenMetadata},
+    private_communication::types::Hash,
     transceiver::{
-        msg::ExecuteMsg,
         state::{
-            CHANNELS, COLLECTIONS, CONFIG, DENOM_NTRN, ENC_KEY, IBC_TIMEOUT, IS_PAUSED, OUTPOSTS,
-            TRANSFER_ADMIN_STATE, TRANSFER_ADMIN_TIMEOUT,
+            collections, transfers, CHANNELS, CONFIG, CONTROLS, CONTROL_IDS,
+            CREATE_COLLECTION_REPLY, DENOM_NTRN, ENC_KEY, ENC_KEY_GRACE_PERIOD, IBC_FEE_ESCROW,
+            IS_PAUSED, MAX_IBC_MEMO_LEN, OUTPOSTS, PACKET_ID, PENDING_HOME_COLLECTION,
+            PFM_RECEIVER, PREVIOUS_ENC_KEY, PROCESSED_PACKETS, SAVE_CONTROL_SEQUENCE_REPLY,
+            SAVE_IBC_SEQUENCE_REPLY, TRANSFER_ADMIN_STATE, TRANSFER_ADMIN_TIMEOUT, TRANSFER_IDS,
         },
-        types::{Channel, Collection, Config, Packet, TransceiverType, TransferAdminState},
+        types::{
+            Collection, Config, Control, ControlAction, ControlPacket, ControlStatus, EncKey, Hop,
+            Ics721Memo, NonFungibleTokenPacketData, Outpost, Packet, TimeoutBounds,
+            TransceiverType, Transfer, TransferAdminState, TransferStatus, Transport,
+        },
+    },
+    utils::{
+        check_funds, check_tokens_holder, get_collection_operator_approvals, unwrap_field,
+        FundsType,
     },
-    utils::{check_funds, check_tokens_holder, get_collection_operator_approvals, FundsType},
 };
 
 use crate::helpers::{
-    check_pause_state, get_channel_and_transceiver, get_ibc_transfer_memo, get_ibc_transfer_msg,
-    get_neutron_ibc_transfer_msg, split_address,
+    add_collection, apply_collection_royalty, check_accept_sender, check_pause_state,
+    get_accept_msg, get_carrier_requirement, get_channel, get_channel_and_transceiver,
+    get_collection_royalty, get_control_msg, get_enc_key, get_encrypted_packet,
+    get_forward_channels, get_ibc_fee, get_ibc_hooks_memo, get_ibc_timeout, get_ibc_transfer_memo,
+    get_ibc_transfer_msg, get_ics721_class_id, get_ics721_token_metadata,
+    get_neutron_ibc_transfer_msg, get_receive_msgs, get_token_metadata, parse_ibc_sequence,
+    release_collection_fee, remove_collection, split_address, split_collection_fee, update_config,
+    update_daily_usage,
 };
 
 pub fn try_accept_admin_role(
@@ -94,6 +111,8 @@ pub fn try_update_config(
     hub_address: Option<String>,
     token_limit: Option<u8>,
     min_ntrn_ibc_fee: Option<Uint128>,
+    hub_control: Option<bool>,
+    timeout_bounds: Option<TimeoutBounds>,
 ) -> Result<Response, ContractError> {
     let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
     let mut config = CONFIG.load(deps.storage)?;
@@ -118,31 +137,32 @@ pub fn try_update_config(
         is_config_updated = true;
     }
 
-    if let Some(x) = nft_minter {
-        if config.transceiver_type == TransceiverType::Hub {
-            deps.api.addr_validate(&x)?;
-        }
-
-        config.nft_minter = x;
+    if update_config(
+        deps.api,
+        &mut config,
+        nft_minter,
+        hub_address,
+        token_limit,
+        min_ntrn_ibc_fee,
+    )? {
         is_config_updated = true;
     }
 
-    if let Some(x) = hub_address {
+    if let Some(x) = hub_control {
         if config.transceiver_type == TransceiverType::Hub {
             Err(ContractError::WrongActionType)?;
         }
 
-        config.hub_address = x;
+        config.hub_control = x;
         is_config_updated = true;
     }
 
-    if let Some(x) = token_limit {
-        config.token_limit = x;
-        is_config_updated = true;
-    }
+    if let Some(x) = timeout_bounds {
+        if x.min == 0 || x.min > x.max {
+            Err(ContractError::WrongTimeoutBounds)?;
+        }
 
-    if let Some(x) = min_ntrn_ibc_fee {
-        config.min_ntrn_ibc_fee = x;
+        config.timeout_bounds = x;
         is_config_updated = true;
     }
 
@@ -156,6 +176,89 @@ pub fn try_update_config(
     Ok(Response::new().add_attribute("action", "try_update_config"))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn try_register_outpost(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    from_hub: Option<String>,
+    to_hub: Option<String>,
+    transport: Option<Transport>,
+    route: Option<Vec<Hop>>,
+    chain_id: Option<String>,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    if config.transceiver_type == TransceiverType::Outpost {
+        Err(ContractError::WrongActionType)?;
+    }
+
+    let (hub_prefix, _) = split_address(env.contract.address);
+    let (prefix, _) = split_address(&address);
+
+    // ibc channel isn't required for the outpost on the hub chain
+    let channel = match (from_hub, to_hub) {
+        (None, None) if prefix == hub_prefix => {
+            deps.api.addr_validate(&address)?;
+            None
+        }
+        (from_hub, to_hub) => Some(get_channel(
+            &prefix,
+            &unwrap_field(from_hub, "from_hub")?,
+            &unwrap_field(to_hub, "to_hub")?,
+            transport,
+            route,
+            chain_id,
+            timeout,
+        )?),
+    };
+
+    OUTPOSTS.save(
+        deps.storage,
+        &prefix,
+        &Outpost {
+            prefix: prefix.clone(),
+            address,
+            channel,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "try_register_outpost")
+        .add_attribute("prefix", prefix))
+}
+
+pub fn try_remove_outpost(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    prefix: String,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    if !OUTPOSTS.has(deps.storage, &prefix) {
+        Err(ContractError::OutpostIsNotFound)?;
+    }
+
+    OUTPOSTS.remove(deps.storage, &prefix);
+
+    Ok(Response::new()
+        .add_attribute("action", "try_remove_outpost")
+        .add_attribute("prefix", prefix))
+}
+
 pub fn try_add_collection(
     deps: DepsMut,
     _env: Env,
@@ -170,31 +273,128 @@ pub fn try_add_collection(
         Err(ContractError::Unauthorized)?;
     }
 
-    let collection_list = COLLECTIONS.load(deps.storage)?;
+    add_collection(
+        deps.storage,
+        deps.api,
+        &config,
+        &hub_collection,
+        &home_collection,
+    )?;
+
+    Ok(Response::new().add_attribute("action", "try_add_collection"))
+}
+
+pub fn try_create_collection(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    home_collection: String,
+    name: Option<String>,
+    symbol: Option<String>,
+    flavor: Option<CollectionFlavor>,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    if config.transceiver_type != TransceiverType::Hub {
+        Err(ContractError::WrongActionType)?;
+    }
 
-    if collection_list
-        .iter()
-        .any(|x| x.hub_collection == hub_collection || x.home_collection == home_collection)
+    if collections()
+        .idx
+        .home
+        .item(deps.storage, home_collection.to_owned())?
+        .is_some()
     {
         Err(ContractError::CollectionDuplication)?;
     }
 
-    if config.transceiver_type == TransceiverType::Hub {
-        deps.api.addr_validate(&hub_collection)?;
-    } else {
-        deps.api.addr_validate(&home_collection)?;
-    }
+    let (home_prefix, _) = split_address(&home_collection);
+    let is_remote = split_address(env.contract.address).0 != home_prefix;
+
+    let (name, symbol) = match (name, symbol) {
+        (Some(name), Some(symbol)) => (name, symbol),
+        // remote collection can't be queried
+        _ if is_remote => Err(ContractError::NoParameters)?,
+        (name, symbol) => {
+            let cw721::ContractInfoResponse {
+                name: home_name,
+                symbol: home_symbol,
+            } = deps
+                .querier
+                .query_wasm_smart(&home_collection, &cw721::Cw721QueryMsg::ContractInfo {})?;
+
+            (name.unwrap_or(home_name), symbol.unwrap_or(home_symbol))
+        }
+    };
 
-    COLLECTIONS.update(deps.storage, |mut collection_list| -> StdResult<_> {
-        collection_list.push(Collection {
-            home_collection,
-            hub_collection,
-        });
+    // remote collection royalty is carried by packets
+    let royalty_info = get_collection_royalty(deps.querier, &home_collection);
+    PENDING_HOME_COLLECTION.save(deps.storage, &home_collection)?;
+
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.nft_minter,
+        msg: to_json_binary(&snb_base::nft_minter::msg::ExecuteMsg::CreateCollection {
+            name: name.clone(),
+            symbol: Some(symbol),
+            description: None,
+            image: None,
+            external_link: None,
+            label: None,
+            flavor,
+            royalty_info,
+            minter: None,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(msg, CREATE_COLLECTION_REPLY))
+        .add_attribute("action", "try_create_collection")
+        .add_attribute("home_collection", home_collection)
+        .add_attribute("name", name))
+}
 
-        Ok(collection_list)
-    })?;
+pub fn save_hub_collection(
+    deps: DepsMut,
+    _env: Env,
+    result: &SubMsgResult,
+) -> Result<Response, ContractError> {
+    let res = result
+        .to_owned()
+        .into_result()
+        .map_err(|e| ContractError::CustomError { val: e })?;
+
+    let instantiate_event = unwrap_field(
+        res.events.iter().find(|x| x.ty == "instantiate"),
+        "instantiate_event",
+    )?;
 
-    Ok(Response::new().add_attribute("action", "try_add_collection"))
+    let hub_collection = &unwrap_field(
+        instantiate_event
+            .attributes
+            .iter()
+            .find(|x| x.key == "_contract_address"),
+        "hub_collection",
+    )?
+    .value;
+
+    let home_collection = PENDING_HOME_COLLECTION.load(deps.storage)?;
+    PENDING_HOME_COLLECTION.remove(deps.storage);
+
+    collections().save(
+        deps.storage,
+        hub_collection,
+        &Collection::new(hub_collection, &home_collection),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("hub_collection", hub_collection)
+        .add_attribute("home_collection", home_collection))
 }
 
 pub fn try_remove_collection(
@@ -210,15 +410,70 @@ pub fn try_remove_collection(
         Err(ContractError::Unauthorized)?;
     }
 
-    COLLECTIONS.update(deps.storage, |mut collection_list| -> StdResult<_> {
-        collection_list.retain(|x| x.hub_collection != hub_collection);
-
-        Ok(collection_list)
-    })?;
+    remove_collection(deps.storage, &hub_collection)?;
 
     Ok(Response::new().add_attribute("action", "try_remove_collection"))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn try_update_collection(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hub_collection: String,
+    token_limit: Option<u8>,
+    is_paused: Option<bool>,
+    fee: Option<Coin>,
+    daily_quota: Option<u32>,
+    royalty_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    let mut collection = collections()
+        .may_load(deps.storage, &hub_collection)?
+        .ok_or(ContractError::CollectionIsNotFound)?;
+
+    if let Some(x) = token_limit {
+        collection.token_limit = if x == 0 { None } else { Some(x) };
+    }
+
+    if let Some(x) = is_paused {
+        collection.is_paused = x;
+    }
+
+    if let Some(x) = fee {
+        collection.fee = if x.amount.is_zero() { None } else { Some(x) };
+    }
+
+    if let Some(x) = daily_quota {
+        collection.daily_quota = if x == 0 { None } else { Some(x) };
+    }
+
+    if let Some(x) = royalty_recipient {
+        if config.transceiver_type != TransceiverType::Hub {
+            Err(ContractError::WrongActionType)?;
+        }
+
+        collection.royalty_recipient = if x.is_empty() {
+            None
+        } else {
+            Some(deps.api.addr_validate(&x)?.to_string())
+        };
+    }
+
+    collections().save(deps.storage, &hub_collection, &collection)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "try_update_collection")
+        .add_attribute("hub_collection", hub_collection))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn try_set_channel(
     deps: DepsMut,
     _env: Env,
@@ -226,6 +481,10 @@ pub fn try_set_channel(
     prefix: String,
     from_hub: String,
     to_hub: String,
+    transport: Option<Transport>,
+    route: Option<Vec<Hop>>,
+    chain_id: Option<String>,
+    timeout: Option<u64>,
 ) -> Result<Response, ContractError> {
     let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
     let config = CONFIG.load(deps.storage)?;
@@ -234,14 +493,129 @@ pub fn try_set_channel(
         Err(ContractError::Unauthorized)?;
     }
 
-    CHANNELS.update(deps.storage, |mut channel_list| -> StdResult<_> {
-        channel_list.retain(|x| x.prefix == prefix);
-        channel_list.push(Channel::new(&prefix, &from_hub, &to_hub));
+    // the hub uses channels of registered outposts
+    if config.transceiver_type != TransceiverType::Outpost {
+        Err(ContractError::WrongActionType)?;
+    }
 
-        Ok(channel_list)
-    })?;
+    // carrier denoms are managed separately
+    let carrier_denoms = CHANNELS
+        .may_load(deps.storage, &prefix)?
+        .map(|x| x.carrier_denoms)
+        .unwrap_or_default();
+    let channel = get_channel(
+        &prefix, &from_hub, &to_hub, transport, route, chain_id, timeout,
+    )?
+    .with_carrier_denoms(carrier_denoms);
+    CHANNELS.save(deps.storage, &prefix, &channel)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "try_set_channel")
+        .add_attribute("prefix", prefix))
+}
+
+pub fn try_set_carrier_denoms(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    prefix: String,
+    mut denoms: Vec<String>,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    if config.transceiver_type != TransceiverType::Outpost {
+        Err(ContractError::WrongActionType)?;
+    }
+
+    denoms.sort_unstable();
+    denoms.dedup();
+
+    let channel = CHANNELS
+        .may_load(deps.storage, &prefix)?
+        .ok_or(ContractError::ChannelIsNotFound)?;
+    CHANNELS.save(deps.storage, &prefix, &channel.with_carrier_denoms(denoms))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "try_set_carrier_denoms")
+        .add_attribute("prefix", prefix))
+}
+
+pub fn try_remove_channel(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    prefix: String,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    if config.transceiver_type != TransceiverType::Outpost {
+        Err(ContractError::WrongActionType)?;
+    }
+
+    if !CHANNELS.has(deps.storage, &prefix) {
+        Err(ContractError::ChannelIsNotFound)?;
+    }
+
+    CHANNELS.remove(deps.storage, &prefix);
+
+    Ok(Response::new()
+        .add_attribute("action", "try_remove_channel")
+        .add_attribute("prefix", prefix))
+}
+
+pub fn try_rotate_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    enc_key: String,
+) -> Result<Response, ContractError> {
+    let (sender_address, ..) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
 
-    Ok(Response::new().add_attribute("action", "try_set_channel"))
+    Hash::parse(&enc_key)?;
+
+    // the key can be missing if the contract was migrated from the version with hardcoded key
+    let id = match ENC_KEY.may_load(deps.storage)? {
+        Some(previous_key) => {
+            PREVIOUS_ENC_KEY.save(
+                deps.storage,
+                &EncKey {
+                    expiration: Some(env.block.time.plus_seconds(ENC_KEY_GRACE_PERIOD)),
+                    ..previous_key
+                },
+            )?;
+
+            previous_key.id + 1
+        }
+        None => 1,
+    };
+
+    ENC_KEY.save(
+        deps.storage,
+        &EncKey {
+            id,
+            value: enc_key,
+            expiration: None,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "try_rotate_key")
+        .add_attribute("key_id", id.to_string()))
 }
 
 pub fn try_send(
@@ -251,45 +625,78 @@ pub fn try_send(
     hub_collection: String,
     token_list: Vec<String>,
     target: Option<String>,
+    timeout: Option<u64>,
 ) -> Result<Response, ContractError> {
     let mut response = Response::new().add_attribute("action", "try_send");
     check_pause_state(deps.storage)?;
-    let (sender_address, asset_amount, asset_info) = check_funds(
-        deps.as_ref(),
-        &info,
-        FundsType::Single {
-            sender: None,
-            amount: None,
-        },
-    )?;
     let contract_address = &env.contract.address;
-    let timestamp = env.block.time;
     let config = CONFIG.load(deps.storage)?;
-    let collection_list = COLLECTIONS.load(deps.storage)?;
+    let collection = collections()
+        .may_load(deps.storage, &hub_collection)?
+        .ok_or(ContractError::CollectionIsNotFound)?;
     let Collection {
         home_collection,
         hub_collection,
-    } = collection_list
-        .iter()
-        .find(|x| x.hub_collection == hub_collection)
-        .ok_or(ContractError::CollectionIsNotFound)?;
+        ..
+    } = &collection;
+
+    if collection.is_paused {
+        Err(ContractError::CollectionIsPaused)?;
+    }
+
+    // ibc route isn't needed for the same network
+    let route = match target {
+        Some(_) => None,
+        None => Some(get_channel_and_transceiver(
+            deps.storage,
+            &config,
+            home_collection,
+        )?),
+    };
+    // collection fee is escrowed by the transfer, the rest of the funds is checked as usual
+    let info = split_collection_fee(&info, &collection.fee)?;
 
-    // we need 1 token for regular ibc transfer or fee + 1 for ibc transfer from hub
+    // we need 1 token for regular ibc transfer or ack and timeout fees + 1 for transfer from hub
+    // packets sent over the contract's own channel don't require a carrier coin
     let amount_in = Uint128::one();
-    let required_asset_amount =
-        if target.is_none() && config.transceiver_type == TransceiverType::Hub {
-            if asset_info.try_get_native()? != DENOM_NTRN {
-                Err(ContractError::WrongAssetType)?;
-            }
+    let carrier = get_carrier_requirement(
+        deps.querier,
+        &config,
+        route.as_ref().map(|(_, channel, _)| channel),
+    );
+    let (sender_address, asset_info) = if carrier.amount.is_zero() {
+        let (sender_address, _, asset_info) = check_funds(deps.as_ref(), &info, FundsType::Empty)?;
+
+        (sender_address, asset_info)
+    } else {
+        let (sender_address, asset_amount, asset_info) = check_funds(
+            deps.as_ref(),
+            &info,
+            FundsType::Single {
+                sender: None,
+                amount: None,
+            },
+        )?;
+        let denom_in = asset_info.try_get_native()?;
 
-            amount_in + config.min_ntrn_ibc_fee
-        } else {
-            amount_in
-        };
+        if !carrier.denoms.is_empty() && !carrier.denoms.contains(&denom_in) {
+            Err(ContractError::WrongAssetType)?;
+        }
 
-    if asset_amount != required_asset_amount {
-        Err(ContractError::WrongFundsCombination)?;
-    }
+        // overpayment is returned to the sender
+        let change = asset_amount
+            .checked_sub(carrier.amount)
+            .map_err(|_| ContractError::WrongFundsCombination)?;
+
+        if !change.is_zero() {
+            response = response.add_message(BankMsg::Send {
+                to_address: sender_address.to_string(),
+                amount: coins(change.u128(), denom_in),
+            });
+        }
+
+        (sender_address, asset_info)
+    };
 
     let mut tokens = token_list.clone();
     tokens.sort_unstable();
@@ -303,10 +710,12 @@ pub fn try_send(
         Err(ContractError::EmptyTokenList)?;
     }
 
-    if token_list.len() > config.token_limit as usize {
+    if token_list.len() > collection.token_limit.unwrap_or(config.token_limit) as usize {
         Err(ContractError::ExceededTokenLimit)?;
     }
 
+    update_daily_usage(deps.storage, &env.block.time, &collection, token_list.len())?;
+
     // check if nfts are on user balance
     let collection_address = match config.transceiver_type {
         TransceiverType::Outpost => home_collection,
@@ -319,6 +728,8 @@ pub fn try_send(
         collection_address,
         &token_list,
     )?;
+    let metadata_list = get_token_metadata(deps.querier, collection_address, &token_list);
+    let royalty_info = get_collection_royalty(deps.querier, collection_address);
 
     // add transfer msgs
     for token_id in &token_list {
@@ -342,7 +753,7 @@ pub fn try_send(
         )?);
 
         response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.nft_minter,
+            contract_addr: config.nft_minter.clone(),
             msg: to_json_binary(&snb_base::nft_minter::msg::ExecuteMsg::Burn {
                 collection: collection_address.to_owned(),
                 token_list: token_list.clone(),
@@ -365,128 +776,506 @@ pub fn try_send(
         sender_address.to_string()
     };
 
+    let packet_id = PACKET_ID.update(deps.storage, |x| -> StdResult<_> { Ok(x + 1) })?;
     let packet = Packet {
+        id: packet_id,
         sender: contract_address.to_string(),
+        recipient: recipient.clone(),
+        hub_collection: hub_collection.to_owned(),
+        home_collection: home_collection.to_owned(),
+        token_list: token_list.clone(),
+        metadata_list: metadata_list.clone(),
+        royalty_info,
+    };
+    let mut transfer = Transfer {
+        id: packet_id,
+        sender: sender_address,
         recipient,
         hub_collection: hub_collection.to_owned(),
         home_collection: home_collection.to_owned(),
         token_list,
+        metadata_list,
+        carrier: None,
+        ibc_fee: None,
+        fee: collection.fee.clone(),
+        timestamp: env.block.time,
+        channel: None,
+        sequence: None,
+        status: TransferStatus::Delivered,
     };
+    response = response.add_attribute("transfer_id", packet_id.to_string());
 
-    let enc_key = Hash::parse(ENC_KEY)?;
-    let EncryptedResponse { value, timestamp } = serialize_encrypt(&enc_key, &timestamp, &packet)?;
-
-    match target {
+    match (target, route) {
         // same network
-        Some(hub_contract) => {
+        // accept is executed in the same tx so the transfer is delivered immediately
+        (Some(hub_contract), _) => {
+            let encrypted_response = get_encrypted_packet(deps.storage, &env, &packet)?;
+
             response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: hub_contract,
-                msg: to_json_binary(&ExecuteMsg::Accept {
-                    msg: value,
-                    timestamp,
-                })?,
+                msg: to_json_binary(&get_accept_msg(&encrypted_response))?,
                 funds: vec![],
             }));
+            response = response.add_messages(release_collection_fee(&config, &transfer));
+        }
+        // packet is sent over the contract's own channel, the transfer is updated on ack/timeout
+        (None, Some((ibc_channel, channel, _))) if channel.transport != Transport::Ics20 => {
+            let timeout_timestamp = env
+                .block
+                .time
+                .plus_seconds(get_ibc_timeout(&config, &channel, timeout));
+
+            let data = if channel.transport == Transport::Native {
+                to_json_binary(&packet)?
+            } else {
+                let class_id =
+                    get_ics721_class_id(&config, contract_address, &ibc_channel, home_collection);
+                let (token_uris, token_data) =
+                    get_ics721_token_metadata(&packet.metadata_list)?.unzip();
+
+                to_json_binary(&NonFungibleTokenPacketData {
+                    class_id,
+                    class_uri: None,
+                    class_data: None,
+                    token_ids: packet.token_list,
+                    token_uris,
+                    token_data,
+                    sender: transfer.sender.to_string(),
+                    receiver: packet.recipient,
+                    memo: Some(to_json_string(&Ics721Memo {
+                        transfer_id: packet_id,
+                    })?),
+                })?
+            };
+
+            response = response.add_message(IbcMsg::SendPacket {
+                channel_id: ibc_channel.clone(),
+                data,
+                timeout: IbcTimeout::with_timestamp(timeout_timestamp),
+            });
+            response = response.add_attribute("timeout", timeout_timestamp.nanos().to_string());
+
+            transfer = Transfer {
+                channel: Some(ibc_channel),
+                status: TransferStatus::Pending,
+                ..transfer
+            };
         }
         // ibc transfer
-        None => {
-            let outpost_list = OUTPOSTS.load(deps.storage)?;
-            let channel_list = CHANNELS.load(deps.storage)?;
-            let timeout_timestamp_ns = env.block.time.plus_seconds(IBC_TIMEOUT).nanos();
-            let (ibc_channel, target_transceiver) = get_channel_and_transceiver(
-                contract_address,
-                &config.hub_address,
-                home_collection,
-                &outpost_list,
-                &channel_list,
+        (None, route) => {
+            let (ibc_channel, channel, target_transceiver) = unwrap_field(route, "route")?;
+            let encrypted_response = get_encrypted_packet(deps.storage, &env, &packet)?;
+            // multi-hop transfer is sent to packet-forward-middleware of the first hop
+            let forward_channels = get_forward_channels(&config, &channel);
+            let receiver = if forward_channels.is_empty() {
+                target_transceiver.clone()
+            } else {
+                PFM_RECEIVER.to_string()
+            };
+
+            let timeout = get_ibc_timeout(&config, &channel, timeout);
+            let timeout_timestamp_ns = env.block.time.plus_seconds(timeout).nanos();
+            // neutron calls sudo on ack/timeout by itself, other chains require ibc-hooks callback
+            let ibc_callback = match config.transceiver_type {
+                TransceiverType::Hub => None,
+                TransceiverType::Outpost => Some(contract_address),
+            };
+            let ibc_transfer_memo = get_ibc_transfer_memo(
+                &target_transceiver,
+                &encrypted_response,
+                ibc_callback,
+                &forward_channels,
+                timeout,
             )?;
-            let ibc_transfer_memo = get_ibc_transfer_memo(&target_transceiver, &value, timestamp)?;
+
+            // encrypted metadata of all tokens is carried by the memo
+            if ibc_transfer_memo.len() > MAX_IBC_MEMO_LEN {
+                Err(ContractError::ExceededMemoSize {
+                    max: MAX_IBC_MEMO_LEN,
+                })?;
+            }
 
             let denom_in = &asset_info.try_get_native()?;
             let msg = if config.transceiver_type == TransceiverType::Hub {
-                get_neutron_ibc_transfer_msg(
+                let ibc_fee = get_ibc_fee(deps.querier, &config);
+                IBC_FEE_ESCROW.update(deps.storage, |x| -> StdResult<_> {
+                    Ok(x + ibc_fee.total())
+                })?;
+
+                let msg = get_neutron_ibc_transfer_msg(
                     &ibc_channel,
                     denom_in,
                     amount_in,
                     contract_address,
-                    &target_transceiver,
+                    &receiver,
                     timeout_timestamp_ns,
                     &ibc_transfer_memo,
-                    config.min_ntrn_ibc_fee,
-                )
+                    &ibc_fee,
+                );
+                transfer.ibc_fee = Some(ibc_fee);
+
+                msg
             } else {
                 get_ibc_transfer_msg(
                     &ibc_channel,
                     denom_in,
                     amount_in,
                     contract_address,
-                    &target_transceiver,
+                    &receiver,
                     timeout_timestamp_ns,
                     &ibc_transfer_memo,
                 )
             };
 
-            response = response.add_message(msg);
+            // transfer will be refunded on timeout or error
+            transfer = Transfer {
+                carrier: Some(coin(amount_in.u128(), denom_in)),
+                channel: Some(ibc_channel),
+                status: TransferStatus::Pending,
+                ..transfer
+            };
+
+            response = response
+                .add_submessage(SubMsg::reply_on_success(msg, SAVE_IBC_SEQUENCE_REPLY))
+                .add_attribute("timeout", timeout_timestamp_ns.to_string());
         }
     }
 
+    transfers().save(deps.storage, packet_id, &transfer)?;
+
     Ok(response)
 }
 
-pub fn try_accept(
+pub fn save_ibc_sequence(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    result: &SubMsgResult,
+) -> Result<Response, ContractError> {
+    let res = result
+        .to_owned()
+        .into_result()
+        .map_err(|e| ContractError::CustomError { val: e })?;
+
+    let sequence = parse_ibc_sequence(&res.data)?;
+    // the transfer was saved with the last packet id in the same tx
+    let id = PACKET_ID.load(deps.storage)?;
+    let mut transfer = transfers().load(deps.storage, id)?;
+    let channel = unwrap_field(transfer.channel.clone(), "channel")?;
+
+    transfer.sequence = Some(sequence);
+    transfers().save(deps.storage, id, &transfer)?;
+    TRANSFER_IDS.save(deps.storage, (&channel, sequence), &id)?;
+
+    Ok(Response::new()
+        .add_attribute("channel", channel)
+        .add_attribute("sequence", sequence.to_string()))
+}
+
+pub fn try_accept(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     msg: String,
     timestamp: Timestamp,
+    key_id: u64,
+    nonce: Option<Binary>,
 ) -> Result<Response, ContractError> {
     let mut response = Response::new().add_attribute("action", "try_accept");
     let config = CONFIG.load(deps.storage)?;
 
-    let enc_key = Hash::parse(ENC_KEY)?;
+    let enc_key = get_enc_key(deps.storage, &env.block.time, key_id)?;
     let Packet {
+        id,
         sender,
         recipient,
         hub_collection,
-        home_collection,
+        home_collection: _,
         token_list,
-    } = decrypt_deserialize(&enc_key, &timestamp, &msg)?;
+        metadata_list,
+        royalty_info,
+    } = decrypt_deserialize(&enc_key, &timestamp, nonce.as_ref(), &msg)?;
+    let metadata_list = apply_collection_royalty(metadata_list, token_list.len(), royalty_info);
+
+    check_accept_sender(
+        deps.storage,
+        &env.contract.address,
+        &info.sender,
+        &config,
+        &sender,
+    )?;
 
-    match config.transceiver_type {
-        TransceiverType::Hub => {
-            OUTPOSTS.update(deps.storage, |mut x| -> StdResult<_> {
-                if !x.contains(&sender) {
-                    x.push(sender);
-                }
+    if PROCESSED_PACKETS.has(deps.storage, (&sender, id)) {
+        Err(ContractError::PacketDuplication)?;
+    }
 
-                Ok(x)
-            })?;
+    PROCESSED_PACKETS.save(deps.storage, (&sender, id), &true)?;
+    response = response
+        .add_attribute("sender", &sender)
+        .add_attribute("packet_id", id.to_string());
 
-            // mint nfts
-            response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.nft_minter,
-                msg: to_json_binary(&snb_base::nft_minter::msg::ExecuteMsg::Mint {
-                    collection: hub_collection.to_owned(),
-                    token_list,
-                    recipient,
-                })?,
-                funds: vec![],
-            }));
+    let collection = collections()
+        .may_load(deps.storage, &hub_collection)?
+        .ok_or(ContractError::CollectionIsNotFound)?;
+
+    let (msg_list, attributes) =
+        get_receive_msgs(&config, &collection, &token_list, &metadata_list, recipient)?;
+
+    Ok(response.add_messages(msg_list).add_attributes(attributes))
+}
+
+/// control packet is sent over ICS-20 route of the outpost like regular transfer
+pub fn try_send_control(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prefix: String,
+    action: ControlAction,
+) -> Result<Response, ContractError> {
+    let contract_address = &env.contract.address;
+    let config = CONFIG.load(deps.storage)?;
+
+    if config.transceiver_type != TransceiverType::Hub {
+        Err(ContractError::WrongActionType)?;
+    }
+
+    let (sender_address, asset_amount, asset_info) = check_funds(
+        deps.as_ref(),
+        &info,
+        FundsType::Single {
+            sender: None,
+            amount: None,
+        },
+    )?;
+
+    if sender_address != config.admin {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    let denom_in = asset_info.try_get_native()?;
+    let amount_in = Uint128::one();
+
+    if denom_in != DENOM_NTRN {
+        Err(ContractError::WrongAssetType)?;
+    }
+
+    // admin covers both ack and timeout fees, the unused one is returned to the admin
+    let ibc_fee = get_ibc_fee(deps.querier, &config);
+    if asset_amount != amount_in + ibc_fee.total() {
+        Err(ContractError::WrongFundsCombination)?;
+    }
+
+    let Outpost {
+        address, channel, ..
+    } = OUTPOSTS
+        .may_load(deps.storage, &prefix)?
+        .ok_or(ContractError::OutpostIsNotFound)?;
+    let channel = channel.ok_or(ContractError::ChannelIsNotFound)?;
+
+    if channel.transport != Transport::Ics20 {
+        Err(ContractError::ControlIsNotSupported)?;
+    }
+
+    let id = PACKET_ID.update(deps.storage, |x| -> StdResult<_> { Ok(x + 1) })?;
+    let packet = ControlPacket {
+        id,
+        sender: contract_address.to_string(),
+        action: action.clone(),
+    };
+    let encrypted_response = get_encrypted_packet(deps.storage, &env, &packet)?;
+    // multi-hop transfer is sent to packet-forward-middleware of the first hop
+    let forward_channels = get_forward_channels(&config, &channel);
+    let receiver = if forward_channels.is_empty() {
+        address.clone()
+    } else {
+        PFM_RECEIVER.to_string()
+    };
+    let timeout = get_ibc_timeout(&config, &channel, None);
+    let memo = get_ibc_hooks_memo(
+        &address,
+        get_control_msg(&encrypted_response),
+        None,
+        &forward_channels,
+        timeout,
+    )?;
+
+    let msg = get_neutron_ibc_transfer_msg(
+        &channel.from_hub,
+        &denom_in,
+        amount_in,
+        contract_address,
+        &receiver,
+        env.block.time.plus_seconds(timeout).nanos(),
+        &memo,
+        &ibc_fee,
+    );
+    IBC_FEE_ESCROW.update(deps.storage, |x| -> StdResult<_> {
+        Ok(x + ibc_fee.total())
+    })?;
+
+    CONTROLS.save(
+        deps.storage,
+        id,
+        &Control {
+            id,
+            prefix: prefix.clone(),
+            action,
+            carrier: coin(amount_in.u128(), denom_in),
+            ibc_fee: Some(ibc_fee),
+            channel: channel.from_hub,
+            sequence: None,
+            status: ControlStatus::Pending,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(msg, SAVE_CONTROL_SEQUENCE_REPLY))
+        .add_attribute("action", "try_send_control")
+        .add_attribute("control_id", id.to_string())
+        .add_attribute("prefix", prefix))
+}
+
+pub fn save_control_sequence(
+    deps: DepsMut,
+    _env: Env,
+    result: &SubMsgResult,
+) -> Result<Response, ContractError> {
+    let res = result
+        .to_owned()
+        .into_result()
+        .map_err(|e| ContractError::CustomError { val: e })?;
+
+    let sequence = parse_ibc_sequence(&res.data)?;
+    // the control was saved with the last packet id in the same tx
+    let id = PACKET_ID.load(deps.storage)?;
+    let mut control = CONTROLS.load(deps.storage, id)?;
+
+    control.sequence = Some(sequence);
+    CONTROLS.save(deps.storage, id, &control)?;
+    CONTROL_IDS.save(deps.storage, (&control.channel, sequence), &id)?;
+
+    Ok(Response::new()
+        .add_attribute("channel", control.channel)
+        .add_attribute("sequence", sequence.to_string()))
+}
+
+/// applies the hub admin action, failure is returned to the hub as error ack
+pub fn try_control(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: String,
+    timestamp: Timestamp,
+    key_id: u64,
+    nonce: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if config.transceiver_type != TransceiverType::Outpost {
+        Err(ContractError::WrongActionType)?;
+    }
+
+    if !config.hub_control {
+        Err(ContractError::HubControlIsDisabled)?;
+    }
+
+    let enc_key = get_enc_key(deps.storage, &env.block.time, key_id)?;
+    let ControlPacket { id, sender, action } =
+        decrypt_deserialize(&enc_key, &timestamp, nonce.as_ref(), &msg)?;
+
+    check_accept_sender(
+        deps.storage,
+        &env.contract.address,
+        &info.sender,
+        &config,
+        &sender,
+    )?;
+
+    if PROCESSED_PACKETS.has(deps.storage, (&sender, id)) {
+        Err(ContractError::PacketDuplication)?;
+    }
+
+    PROCESSED_PACKETS.save(deps.storage, (&sender, id), &true)?;
+
+    match action {
+        ControlAction::Pause {} => IS_PAUSED.save(deps.storage, &true)?,
+        ControlAction::Unpause {} => IS_PAUSED.save(deps.storage, &false)?,
+        ControlAction::AddCollection {
+            hub_collection,
+            home_collection,
+        } => add_collection(
+            deps.storage,
+            deps.api,
+            &config,
+            &hub_collection,
+            &home_collection,
+        )?,
+        ControlAction::RemoveCollection { hub_collection } => {
+            remove_collection(deps.storage, &hub_collection)?
         }
-        TransceiverType::Outpost => {
-            // unlock nfts
-            for token_id in token_list {
-                response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: home_collection.clone(),
-                    msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
-                        recipient: recipient.clone(),
-                        token_id: token_id.to_string(),
-                    })?,
-                    funds: vec![],
-                }));
+        ControlAction::UpdateConfig {
+            nft_minter,
+            hub_address,
+            token_limit,
+            min_ntrn_ibc_fee,
+        } => {
+            if !update_config(
+                deps.api,
+                &mut config,
+                nft_minter,
+                hub_address,
+                token_limit,
+                min_ntrn_ibc_fee,
+            )? {
+                Err(ContractError::NoParameters)?;
             }
+
+            CONFIG.save(deps.storage, &config)?;
         }
-    };
+    }
 
-    Ok(response)
+    Ok(Response::new()
+        .add_attribute("action", "try_control")
+        .add_attribute("sender", sender)
+        .add_attribute("packet_id", id.to_string()))
+}
+
+/// mints or unlocks nfts received over ibc channel, any failure reverts the whole packet
+#[allow(clippy::too_many_arguments)]
+pub fn try_receive_nfts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hub_collection: String,
+    token_list: Vec<String>,
+    metadata_list: Vec<TokenMetadata>,
+    recipient: String,
+    processed_packet: Option<(String, u64)>,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let collection = collections()
+        .may_load(deps.storage, &hub_collection)?
+        .ok_or(ContractError::CollectionIsNotFound)?;
+
+    let (msg_list, attributes) = get_receive_msgs(
+        &config,
+        &collection,
+        &token_list,
+        &metadata_list,
+        &recipient,
+    )?;
+
+    // rejected packet isn't recorded as its state is reverted
+    if let Some((sender, id)) = processed_packet {
+        PROCESSED_PACKETS.save(deps.storage, (&sender, id), &true)?;
+    }
+
+    Ok(Response::new()
+        .add_messages(msg_list)
+        .add_attributes(attributes)
+        .add_attribute("action", "try_receive_nfts")
+        .add_attribute("recipient", recipient))
 }
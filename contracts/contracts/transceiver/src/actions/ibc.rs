@@ -0,0 +1,327 @@
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, DepsMut, Env, IbcBasicResponse, IbcChannel, IbcOrder,
+    IbcPacket, IbcReceiveResponse, Response, SubMsg, SubMsgResult, WasmMsg,
+};
+
+use snb_base::{
+    error::ContractError,
+    nft_minter::types::TokenMetadata,
+    transceiver::{
+        msg::ExecuteMsg,
+        state::{
+            collections, CHANNEL_VERSIONS, CONFIG, IBC_RECEIVE_REPLY, ICS721_VERSION,
+            NATIVE_VERSION, PROCESSED_PACKETS,
+        },
+        types::{
+            Channel, Collection, Config, IbcAck, NonFungibleTokenPacketData, Packet,
+            TransceiverType, TransferStatus,
+        },
+    },
+};
+
+use crate::helpers::{
+    apply_collection_royalty, check_pause_state, find_pending_transfer, get_collection_by_home,
+    get_ibc_channel, get_ibc_port, get_refund_msgs, parse_ics721_token_metadata,
+    release_collection_fee, revert_daily_usage, set_transfer_status, split_address,
+};
+
+/// (collection, token_list, metadata_list, recipient, (sender, packet id)) of received packet
+type ParsedPacket = (
+    Collection,
+    Vec<String>,
+    Vec<TokenMetadata>,
+    Addr,
+    Option<(String, u64)>,
+);
+
+/// channel can be opened only with ics721 or native transceiver counterparty
+pub fn ibc_open_channel(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if channel.order != IbcOrder::Unordered {
+        Err(ContractError::WrongIbcChannel)?;
+    }
+
+    if channel.version != ICS721_VERSION && channel.version != NATIVE_VERSION {
+        Err(ContractError::WrongIbcChannel)?;
+    }
+
+    if counterparty_version.map_or(false, |x| x != channel.version) {
+        Err(ContractError::WrongIbcChannel)?;
+    }
+
+    // native packets can be sent only by contracts
+    if channel.version == NATIVE_VERSION
+        && !channel.counterparty_endpoint.port_id.starts_with("wasm.")
+    {
+        Err(ContractError::WrongIbcChannel)?;
+    }
+
+    Ok(())
+}
+
+/// negotiated version defines the format of packets received over the channel
+pub fn ibc_connect_channel(
+    deps: DepsMut,
+    _env: Env,
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<IbcBasicResponse, ContractError> {
+    ibc_open_channel(channel, counterparty_version)?;
+    CHANNEL_VERSIONS.save(deps.storage, &channel.endpoint.channel_id, &channel.version)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_connect_channel")
+        .add_attribute("channel", &channel.endpoint.channel_id)
+        .add_attribute("version", &channel.version))
+}
+
+/// the channel can be closed by the counterparty, pending transfers may be already received
+/// so they are refunded only on timeout on close
+pub fn ibc_close_channel(
+    deps: DepsMut,
+    _env: Env,
+    channel: &IbcChannel,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = &channel.endpoint.channel_id;
+    CHANNEL_VERSIONS.remove(deps.storage, channel_id);
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_close_channel")
+        .add_attribute("channel", channel_id))
+}
+
+/// hub mints nfts sent from the outpost, outpost unlocks nfts sent back from the hub
+pub fn ibc_receive(
+    deps: DepsMut,
+    env: Env,
+    packet: &IbcPacket,
+) -> Result<IbcReceiveResponse, ContractError> {
+    check_pause_state(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let (channel, counterparty) = get_ibc_channel(
+        deps.storage,
+        &env.contract.address,
+        &config,
+        &packet.dest.channel_id,
+    )?;
+
+    let version = CHANNEL_VERSIONS
+        .may_load(deps.storage, &packet.dest.channel_id)?
+        .ok_or(ContractError::ChannelIsNotFound)?;
+
+    let (collection, token_list, metadata_list, recipient, processed_packet) =
+        match version.as_str() {
+            NATIVE_VERSION => parse_native_packet(deps, &channel, &counterparty, packet)?,
+            ICS721_VERSION => parse_ics721_packet(deps, &config, &channel, packet)?,
+            _ => Err(ContractError::WrongIbcChannel)?,
+        };
+
+    if collection.is_paused {
+        Err(ContractError::CollectionIsPaused)?;
+    }
+
+    // all nfts are received in a single submessage, partial unlock would duplicate the tokens
+    // refunded on the counterparty chain
+    let msg = WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_json_binary(&ExecuteMsg::ReceiveNfts {
+            hub_collection: collection.hub_collection,
+            token_list,
+            metadata_list,
+            recipient: recipient.to_string(),
+            processed_packet,
+        })?,
+        funds: vec![],
+    };
+
+    // failed mint or unlock is returned to the sender as error ack
+    Ok(IbcReceiveResponse::new()
+        .set_ack(to_json_binary(&IbcAck::Result(Binary(vec![1])))?)
+        .add_submessage(SubMsg::reply_on_error(msg, IBC_RECEIVE_REPLY))
+        .add_attribute("action", "ibc_receive")
+        .add_attribute("channel", &packet.dest.channel_id)
+        .add_attribute("recipient", recipient))
+}
+
+/// returns parsed packet sent by counterparty transceiver
+fn parse_native_packet(
+    deps: DepsMut,
+    channel: &Channel,
+    counterparty: &str,
+    packet: &IbcPacket,
+) -> Result<ParsedPacket, ContractError> {
+    let Packet {
+        id,
+        sender,
+        recipient,
+        hub_collection,
+        home_collection,
+        token_list,
+        metadata_list,
+        royalty_info,
+    } = from_json(&packet.data)?;
+    let metadata_list = apply_collection_royalty(metadata_list, token_list.len(), royalty_info);
+
+    // only registered transceiver can send packets over the channel
+    if packet.src.port_id != get_ibc_port(counterparty) || sender != counterparty {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    if token_list.is_empty() {
+        Err(ContractError::EmptyTokenList)?;
+    }
+
+    let collection = collections()
+        .may_load(deps.storage, &hub_collection)?
+        .filter(|x| x.home_collection == home_collection)
+        .ok_or(ContractError::CollectionIsNotFound)?;
+
+    // collection must be native for the outpost network
+    if split_address(&collection.home_collection).0 != channel.prefix {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    // the packet is saved as processed only if the nfts are received
+    if PROCESSED_PACKETS.has(deps.storage, (&sender, id)) {
+        Err(ContractError::PacketDuplication)?;
+    }
+
+    Ok((
+        collection,
+        token_list,
+        metadata_list,
+        recipient,
+        Some((sender, id)),
+    ))
+}
+
+/// returns parsed standard ics721 packet, it has no packet id
+fn parse_ics721_packet(
+    deps: DepsMut,
+    config: &Config,
+    channel: &Channel,
+    packet: &IbcPacket,
+) -> Result<ParsedPacket, ContractError> {
+    let NonFungibleTokenPacketData {
+        class_id,
+        token_ids,
+        token_uris,
+        token_data,
+        receiver,
+        ..
+    } = from_json(&packet.data)?;
+    let recipient = deps.api.addr_validate(&receiver)?;
+
+    if token_ids.is_empty() {
+        Err(ContractError::EmptyTokenList)?;
+    }
+
+    // class id is prefixed by the sender if the nfts are returned to their source network
+    let source_prefix = format!("{}/{}/", packet.src.port_id, packet.src.channel_id);
+    let home_collection = match config.transceiver_type {
+        TransceiverType::Hub => {
+            if class_id.starts_with(&source_prefix) {
+                Err(ContractError::WrongClassId)?;
+            }
+
+            class_id.as_str()
+        }
+        TransceiverType::Outpost => class_id
+            .strip_prefix(&source_prefix)
+            .ok_or(ContractError::WrongClassId)?,
+    };
+
+    let collection = get_collection_by_home(deps.storage, home_collection)?;
+
+    // collection must be native for the outpost network
+    if split_address(&collection.home_collection).0 != channel.prefix {
+        Err(ContractError::WrongClassId)?;
+    }
+
+    let metadata_list = parse_ics721_token_metadata(token_ids.len(), token_uris, token_data);
+
+    Ok((collection, token_ids, metadata_list, recipient, None))
+}
+
+/// replaces the success ack when mint or unlock is failed
+pub fn ibc_receive_failed(
+    _deps: DepsMut,
+    _env: Env,
+    result: &SubMsgResult,
+) -> Result<Response, ContractError> {
+    let error = match result {
+        SubMsgResult::Err(x) => x.to_owned(),
+        SubMsgResult::Ok(_) => String::default(),
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "ibc_receive_failed")
+        .set_data(get_error_ack(&error)))
+}
+
+/// transfer is delivered on success ack or refunded on error ack
+pub fn ibc_ack(
+    deps: DepsMut,
+    env: Env,
+    packet: &IbcPacket,
+    acknowledgement: &Binary,
+) -> Result<IbcBasicResponse, ContractError> {
+    match from_json::<IbcAck>(acknowledgement) {
+        Ok(IbcAck::Result(_)) => {
+            let response = IbcBasicResponse::new()
+                .add_attribute("action", "ibc_ack")
+                .add_attribute("channel", &packet.src.channel_id)
+                .add_attribute("sequence", packet.sequence.to_string());
+
+            // the packet can't be found if it wasn't sent by the contract
+            let transfer = match find_pending_transfer(deps.storage, packet)? {
+                Some(x) => x,
+                None => return Ok(response.add_attribute("transfer", "unknown")),
+            };
+
+            let config = CONFIG.load(deps.storage)?;
+            let transfer =
+                set_transfer_status(deps.storage, transfer.id, TransferStatus::Delivered)?;
+
+            Ok(response
+                .add_messages(release_collection_fee(&config, &transfer))
+                .add_attribute("transfer_id", transfer.id.to_string()))
+        }
+        _ => ibc_timeout(deps, env, packet),
+    }
+}
+
+/// transfer is failed, nfts must be returned to the sender
+pub fn ibc_timeout(
+    deps: DepsMut,
+    _env: Env,
+    packet: &IbcPacket,
+) -> Result<IbcBasicResponse, ContractError> {
+    let response = IbcBasicResponse::new()
+        .add_attribute("action", "ibc_refund")
+        .add_attribute("channel", &packet.src.channel_id)
+        .add_attribute("sequence", packet.sequence.to_string());
+
+    let config = CONFIG.load(deps.storage)?;
+    // the packet can't be found if it wasn't sent by the contract
+    let transfer = match find_pending_transfer(deps.storage, packet)? {
+        Some(x) => x,
+        None => return Ok(response.add_attribute("transfer", "unknown")),
+    };
+    let transfer = set_transfer_status(deps.storage, transfer.id, TransferStatus::Refunded)?;
+    revert_daily_usage(deps.storage, &transfer)?;
+
+    Ok(response
+        .add_messages(get_refund_msgs(&config, &transfer)?)
+        .add_attribute("transfer_id", transfer.id.to_string())
+        .add_attribute("sender", transfer.sender))
+}
+
+pub fn get_error_ack(error: &str) -> Binary {
+    // serialization of the enum can't fail
+    to_json_binary(&IbcAck::Error(error.to_string())).unwrap_or_default()
+}
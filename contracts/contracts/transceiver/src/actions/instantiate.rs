@@ -3,13 +3,15 @@ use cw2::set_contract_version;
 
 use snb_base::{
     error::ContractError,
+    private_communication::types::Hash,
     transceiver::{
         msg::InstantiateMsg,
         state::{
-            CHANNELS, CHANNEL_NEUTRON_STARGAZE, CHANNEL_STARGAZE_NEUTRON, COLLECTIONS, CONFIG,
-            CONTRACT_NAME, IS_PAUSED, MIN_NTRN_IBC_FEE, OUTPOSTS, PREFIX_STARGAZE, TOKEN_LIMIT,
+            CHANNELS, CHANNEL_NEUTRON_STARGAZE, CHANNEL_STARGAZE_NEUTRON, CONFIG, CONTRACT_NAME,
+            ENC_KEY, IBC_FEE_ESCROW, IS_PAUSED, MIN_NTRN_IBC_FEE, NONCE_COUNTER, PACKET_ID,
+            PREFIX_STARGAZE, TOKEN_LIMIT,
         },
-        types::{Channel, Config, TransceiverType},
+        types::{Channel, Config, EncKey, TimeoutBounds, TransceiverType},
     },
 };
 
@@ -30,6 +32,8 @@ pub fn try_instantiate(
         msg.hub_address.unwrap_or_default()
     };
 
+    Hash::parse(&msg.enc_key)?;
+
     IS_PAUSED.save(deps.storage, &false)?;
     CONFIG.save(
         deps.storage,
@@ -37,24 +41,40 @@ pub fn try_instantiate(
             admin: sender.to_owned(),
             nft_minter: msg.nft_minter.unwrap_or_default(),
             hub_address,
-            transceiver_type: msg.transceiver_type,
+            transceiver_type: msg.transceiver_type.clone(),
             token_limit: msg.token_limit.unwrap_or(TOKEN_LIMIT),
             min_ntrn_ibc_fee: msg
                 .min_ntrn_ibc_fee
                 .unwrap_or(Uint128::new(MIN_NTRN_IBC_FEE)),
+            hub_control: false,
+            timeout_bounds: TimeoutBounds::default(),
         },
     )?;
 
-    OUTPOSTS.save(deps.storage, &vec![])?;
-    COLLECTIONS.save(deps.storage, &vec![])?;
-    CHANNELS.save(
+    ENC_KEY.save(
         deps.storage,
-        &vec![Channel::new(
-            PREFIX_STARGAZE,
-            CHANNEL_NEUTRON_STARGAZE,
-            CHANNEL_STARGAZE_NEUTRON,
-        )],
+        &EncKey {
+            id: 1,
+            value: msg.enc_key,
+            expiration: None,
+        },
     )?;
+    NONCE_COUNTER.save(deps.storage, &0)?;
+    PACKET_ID.save(deps.storage, &0)?;
+    IBC_FEE_ESCROW.save(deps.storage, &Uint128::zero())?;
+
+    // the hub uses channels of registered outposts
+    if msg.transceiver_type == TransceiverType::Outpost {
+        CHANNELS.save(
+            deps.storage,
+            PREFIX_STARGAZE,
+            &Channel::new(
+                PREFIX_STARGAZE,
+                CHANNEL_NEUTRON_STARGAZE,
+                CHANNEL_STARGAZE_NEUTRON,
+            ),
+        )?;
+    }
 
     Ok(Response::new().add_attribute("action", "try_instantiate"))
 }
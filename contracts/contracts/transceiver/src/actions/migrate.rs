@@ -1,11 +1,21 @@
-use cosmwasm_std::{DepsMut, Env, Response, Storage};
+use cosmwasm_std::{DepsMut, Env, Response, Storage, Uint128};
 use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Item;
 
 use semver::Version;
 
 use snb_base::{
     error::ContractError,
-    transceiver::{msg::MigrateMsg, state::CONTRACT_NAME},
+    private_communication::types::Hash,
+    transceiver::{
+        msg::MigrateMsg,
+        state::{
+            collections, CHANNELS, CONFIG, CONTRACT_NAME, ENC_KEY, IBC_FEE_ESCROW,
+            LEGACY_ENC_KEY_ID, NONCE_COUNTER, PACKET_ID,
+        },
+        types::{Channel, Collection, EncKey, TransceiverType},
+    },
+    utils::unwrap_field,
 };
 
 pub fn migrate_contract(
@@ -13,18 +23,74 @@ pub fn migrate_contract(
     _env: Env,
     msg: MigrateMsg,
 ) -> Result<Response, ContractError> {
-    let (version_previous, version_new) = get_versions(deps.storage, msg)?;
+    let (version_previous, version_new) = get_versions(deps.storage, &msg)?;
 
     if version_new >= version_previous {
         set_contract_version(deps.storage, CONTRACT_NAME, version_new.to_string())?;
     }
 
+    // init state added after first deployment
+    if !PACKET_ID.exists(deps.storage) {
+        PACKET_ID.save(deps.storage, &0)?;
+    }
+
+    if !NONCE_COUNTER.exists(deps.storage) {
+        NONCE_COUNTER.save(deps.storage, &0)?;
+    }
+
+    if !IBC_FEE_ESCROW.exists(deps.storage) {
+        IBC_FEE_ESCROW.save(deps.storage, &Uint128::zero())?;
+    }
+
+    // hardcoded key is replaced with the key provided by admin, it's registered under the id
+    // of legacy packets to decrypt them until the key is rotated
+    if !ENC_KEY.exists(deps.storage) {
+        let enc_key = unwrap_field(msg.enc_key, "enc_key")?;
+        Hash::parse(&enc_key)?;
+
+        ENC_KEY.save(
+            deps.storage,
+            &EncKey {
+                id: LEGACY_ENC_KEY_ID,
+                value: enc_key,
+                expiration: None,
+            },
+        )?;
+    }
+
+    // auto-registered outposts can't be trusted, admin must register them again
+    Item::<Vec<String>>::new("outposts").remove(deps.storage);
+
+    // move channels from the list to the map, the hub uses channels of registered outposts
+    let legacy_channels = Item::<Vec<Channel>>::new("channels");
+
+    if let Some(channel_list) = legacy_channels.may_load(deps.storage)? {
+        legacy_channels.remove(deps.storage);
+
+        if CONFIG.load(deps.storage)?.transceiver_type == TransceiverType::Outpost {
+            for channel in channel_list {
+                CHANNELS.save(deps.storage, &channel.prefix, &channel)?;
+            }
+        }
+    }
+
+    // move collections from the list to the indexed map
+    let legacy_collections = Item::<Vec<Collection>>::new("collections");
+
+    if let Some(collection_list) = legacy_collections.may_load(deps.storage)? {
+        legacy_collections.remove(deps.storage);
+
+        for collection in collection_list {
+            collections().save(deps.storage, &collection.hub_collection, &collection)?;
+        }
+    }
+
     Ok(Response::new())
 }
 
 fn get_versions(
     storage: &dyn Storage,
-    msg: MigrateMsg,
+    msg: &MigrateMsg,
 ) -> Result<(Version, Version), ContractError> {
     let version_previous: Version = get_contract_version(storage)?
         .version
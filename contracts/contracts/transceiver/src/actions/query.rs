@@ -1,13 +1,25 @@
-use cosmwasm_std::{Deps, Env, StdResult};
+use cosmwasm_std::{Deps, Env, Order, StdResult};
+
+use cw_storage_plus::Bound;
 
 use snb_base::{
     error::ContractError,
     transceiver::{
-        state::{CHANNELS, COLLECTIONS, CONFIG, IS_PAUSED, OUTPOSTS},
-        types::{Channel, Collection, Config},
+        state::{
+            collections, transfers, CHANNELS, CONFIG, CONTROLS, DENOM_NTRN, IBC_FEE_ESCROW,
+            IS_PAUSED, OUTPOSTS, PAGINATION_DEFAULT_LIMIT, PAGINATION_MAX_LIMIT, PROCESSED_PACKETS,
+        },
+        types::{
+            CarrierRequirement, Channel, Collection, Config, Control, FeeBalance, IbcFee, Outpost,
+            TransceiverType, Transfer, TransferStatus,
+        },
     },
 };
 
+use crate::helpers::{
+    get_carrier_requirement, get_channel_and_transceiver, get_collection_by_home, get_ibc_fee,
+};
+
 pub fn query_config(deps: Deps, _env: Env) -> StdResult<Config> {
     CONFIG.load(deps.storage)
 }
@@ -16,8 +28,19 @@ pub fn query_pause_state(deps: Deps, _env: Env) -> StdResult<bool> {
     IS_PAUSED.load(deps.storage)
 }
 
-pub fn query_outposts(deps: Deps, _env: Env) -> StdResult<Vec<String>> {
-    OUTPOSTS.load(deps.storage)
+pub fn query_outposts(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Outpost>> {
+    let start_bound = start_after.as_deref().map(Bound::exclusive);
+
+    OUTPOSTS
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(get_limit(limit))
+        .map(|x| x.map(|(_, outpost)| outpost))
+        .collect()
 }
 
 pub fn query_collection(
@@ -26,39 +49,161 @@ pub fn query_collection(
     hub_collection: Option<String>,
     home_collection: Option<String>,
 ) -> StdResult<Collection> {
-    let collections = COLLECTIONS.load(deps.storage)?;
-
     if let Some(x) = hub_collection {
-        return collections
-            .into_iter()
-            .find(|y| y.hub_collection == x)
+        return collections()
+            .may_load(deps.storage, &x)?
             .ok_or(ContractError::CollectionIsNotFound.into());
     }
 
     if let Some(x) = home_collection {
-        return collections
-            .into_iter()
-            .find(|y| y.home_collection == x)
-            .ok_or(ContractError::CollectionIsNotFound.into());
+        return get_collection_by_home(deps.storage, &x);
     }
 
     Err(ContractError::NoParameters)?
 }
 
-pub fn query_collection_list(deps: Deps, _env: Env) -> StdResult<Vec<Collection>> {
-    COLLECTIONS.load(deps.storage)
+pub fn query_collection_list(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Collection>> {
+    let start_bound = start_after.as_deref().map(Bound::exclusive);
+
+    collections()
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(get_limit(limit))
+        .map(|x| x.map(|(_, collection)| collection))
+        .collect()
 }
 
-pub fn query_channel_list(deps: Deps, _env: Env) -> StdResult<Vec<Channel>> {
-    CHANNELS.load(deps.storage)
+pub fn query_carrier_requirement(
+    deps: Deps,
+    _env: Env,
+    hub_collection: String,
+) -> StdResult<CarrierRequirement> {
+    let config = CONFIG.load(deps.storage)?;
+    let Collection {
+        home_collection, ..
+    } = collections()
+        .may_load(deps.storage, &hub_collection)?
+        .ok_or(ContractError::CollectionIsNotFound)?;
+    let (_, channel, _) = get_channel_and_transceiver(deps.storage, &config, &home_collection)?;
+
+    Ok(get_carrier_requirement(
+        deps.querier,
+        &config,
+        Some(&channel),
+    ))
+}
+
+pub fn query_fee_balance(deps: Deps, env: Env) -> StdResult<FeeBalance> {
+    Ok(FeeBalance {
+        escrowed: IBC_FEE_ESCROW.load(deps.storage)?,
+        balance: deps
+            .querier
+            .query_balance(env.contract.address, DENOM_NTRN)?
+            .amount,
+    })
+}
+
+pub fn query_fee_estimate(deps: Deps, _env: Env) -> StdResult<IbcFee> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if config.transceiver_type != TransceiverType::Hub {
+        Err(ContractError::WrongActionType)?;
+    }
+
+    Ok(get_ibc_fee(deps.querier, &config))
 }
 
-// pub fn query_fee(deps: Deps, _env: Env) -> StdResult<Vec<Channel>> {
-//     let request = QueryRequest::Stargate {
-//         path: "/neutron.interchaintxs.v1.Query/Params".to_string(),
-//         data: to_json_binary("")?,
-//     };
-//     let a: String = deps.querier.query(&request)?;
+pub fn query_channel_list(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Channel>> {
+    let start_bound = start_after.as_deref().map(Bound::exclusive);
+
+    CHANNELS
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(get_limit(limit))
+        .map(|x| x.map(|(_, channel)| channel))
+        .collect()
+}
 
-//     unimplemented!()
-// }
+pub fn query_transfer(deps: Deps, _env: Env, id: u64) -> StdResult<Transfer> {
+    transfers().load(deps.storage, id)
+}
+
+pub fn query_transfers_by_sender(
+    deps: Deps,
+    _env: Env,
+    sender: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Transfer>> {
+    let sender = deps.api.addr_validate(&sender)?;
+    let start_bound = start_after.map(Bound::exclusive);
+
+    transfers()
+        .idx
+        .sender
+        .prefix(sender)
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(get_limit(limit))
+        .map(|x| x.map(|(_, transfer)| transfer))
+        .collect()
+}
+
+pub fn query_pending_transfers(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Transfer>> {
+    let start_bound = start_after.map(Bound::exclusive);
+
+    transfers()
+        .idx
+        .status
+        .prefix(TransferStatus::Pending.to_string())
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(get_limit(limit))
+        .map(|x| x.map(|(_, transfer)| transfer))
+        .collect()
+}
+
+pub fn query_control(deps: Deps, _env: Env, id: u64) -> StdResult<Control> {
+    CONTROLS.load(deps.storage, id)
+}
+
+pub fn query_control_list(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Control>> {
+    let start_bound = start_after.map(Bound::exclusive);
+
+    CONTROLS
+        .range(deps.storage, start_bound, None, Order::Ascending)
+        .take(get_limit(limit))
+        .map(|x| x.map(|(_, control)| control))
+        .collect()
+}
+
+fn get_limit(limit: Option<u32>) -> usize {
+    limit
+        .unwrap_or(PAGINATION_DEFAULT_LIMIT)
+        .min(PAGINATION_MAX_LIMIT) as usize
+}
+
+pub fn query_packet_state(
+    deps: Deps,
+    _env: Env,
+    sender: String,
+    packet_id: u64,
+) -> StdResult<bool> {
+    Ok(PROCESSED_PACKETS.has(deps.storage, (&sender, packet_id)))
+}
@@ -0,0 +1,148 @@
+use cosmwasm_std::{BankMsg, DepsMut, Env, Response, StdResult, Storage};
+
+use snb_base::{
+    error::ContractError,
+    transceiver::{
+        state::{CONFIG, CONTROLS, CONTROL_IDS, TRANSFER_IDS},
+        types::{Control, ControlStatus, RequestPacket, TransferStatus},
+    },
+    utils::unwrap_field,
+};
+
+use crate::helpers::{
+    get_refund_msgs, release_collection_fee, release_ibc_fee, revert_daily_usage,
+    set_transfer_status,
+};
+
+/// returns (source_channel, sequence) of neutron request packet
+pub fn parse_request_packet(request: &RequestPacket) -> Result<(String, u64), ContractError> {
+    Ok((
+        unwrap_field(request.source_channel.to_owned(), "source_channel")?,
+        unwrap_field(request.sequence, "sequence")?,
+    ))
+}
+
+/// transfer is delivered
+pub fn sudo_ack(
+    deps: DepsMut,
+    _env: Env,
+    channel: String,
+    sequence: u64,
+) -> Result<Response, ContractError> {
+    let response = Response::new()
+        .add_attribute("action", "sudo_ack")
+        .add_attribute("channel", &channel)
+        .add_attribute("sequence", sequence.to_string());
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // the callback can be received for transfers not related to bridging
+    let id = match TRANSFER_IDS.may_load(deps.storage, (&channel, sequence))? {
+        Some(x) => x,
+        None => {
+            return Ok(
+                match set_control_status(deps.storage, &channel, sequence, ControlStatus::Applied)?
+                {
+                    Some(x) => response
+                        .add_messages(release_ibc_fee(
+                            deps.storage,
+                            &x.ibc_fee,
+                            config.admin.as_str(),
+                            false,
+                        )?)
+                        .add_attribute("control_id", x.id.to_string()),
+                    None => response,
+                },
+            )
+        }
+    };
+
+    TRANSFER_IDS.remove(deps.storage, (&channel, sequence));
+    let transfer = set_transfer_status(deps.storage, id, TransferStatus::Delivered)?;
+
+    Ok(response
+        .add_messages(release_ibc_fee(
+            deps.storage,
+            &transfer.ibc_fee,
+            transfer.sender.as_str(),
+            false,
+        )?)
+        .add_messages(release_collection_fee(&config, &transfer))
+        .add_attribute("transfer_id", id.to_string()))
+}
+
+/// transfer is failed, nfts, carrier coin and collection fee must be returned to the sender
+pub fn sudo_refund(
+    deps: DepsMut,
+    _env: Env,
+    channel: String,
+    sequence: u64,
+    is_timeout: bool,
+) -> Result<Response, ContractError> {
+    let response = Response::new()
+        .add_attribute("action", "sudo_refund")
+        .add_attribute("channel", &channel)
+        .add_attribute("sequence", sequence.to_string());
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // the callback can be received for transfers not related to bridging
+    let id = match TRANSFER_IDS.may_load(deps.storage, (&channel, sequence))? {
+        Some(x) => x,
+        None => {
+            return Ok(
+                match set_control_status(deps.storage, &channel, sequence, ControlStatus::Failed)? {
+                    // carrier coin and unused ibc fee are returned to the admin
+                    Some(x) => response
+                        .add_message(BankMsg::Send {
+                            to_address: config.admin.to_string(),
+                            amount: vec![x.carrier],
+                        })
+                        .add_messages(release_ibc_fee(
+                            deps.storage,
+                            &x.ibc_fee,
+                            config.admin.as_str(),
+                            is_timeout,
+                        )?)
+                        .add_attribute("control_id", x.id.to_string()),
+                    None => response,
+                },
+            );
+        }
+    };
+
+    TRANSFER_IDS.remove(deps.storage, (&channel, sequence));
+    let transfer = set_transfer_status(deps.storage, id, TransferStatus::Refunded)?;
+    revert_daily_usage(deps.storage, &transfer)?;
+
+    Ok(response
+        .add_messages(get_refund_msgs(&config, &transfer)?)
+        .add_messages(release_ibc_fee(
+            deps.storage,
+            &transfer.ibc_fee,
+            transfer.sender.as_str(),
+            is_timeout,
+        )?)
+        .add_attribute("transfer_id", id.to_string())
+        .add_attribute("sender", transfer.sender))
+}
+
+/// returns updated control packet if the ibc transfer was sent to deliver it
+fn set_control_status(
+    storage: &mut dyn Storage,
+    channel: &str,
+    sequence: u64,
+    status: ControlStatus,
+) -> StdResult<Option<Control>> {
+    let id = match CONTROL_IDS.may_load(storage, (channel, sequence))? {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+
+    CONTROL_IDS.remove(storage, (channel, sequence));
+    let mut control = CONTROLS.load(storage, id)?;
+    control.status = status;
+    CONTROLS.save(storage, id, &control)?;
+
+    Ok(Some(control))
+}
@@ -1,15 +1,26 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, IbcBasicResponse, IbcChannelCloseMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcPacketAckMsg,
+    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, MessageInfo, Reply, Response,
+    StdResult,
 };
 
 use snb_base::{
-    error::ContractError,
-    transceiver::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg},
+    error::{ContractError, Never},
+    transceiver::{
+        msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg},
+        state::{
+            CREATE_COLLECTION_REPLY, IBC_RECEIVE_REPLY, SAVE_CONTROL_SEQUENCE_REPLY,
+            SAVE_IBC_SEQUENCE_REPLY,
+        },
+        types::IbcLifecycleComplete,
+    },
 };
 
 use crate::actions::{
-    execute as e, instantiate::try_instantiate, migrate::migrate_contract, query as q,
+    execute as e, ibc as i, instantiate::try_instantiate, migrate::migrate_contract, query as q,
+    sudo as s,
 };
 
 /// Creates a new contract with the specified parameters packed in the "msg" variable
@@ -44,6 +55,8 @@ pub fn execute(
             hub_address,
             token_limit,
             min_ntrn_ibc_fee,
+            hub_control,
+            timeout_bounds,
         } => e::try_update_config(
             deps,
             env,
@@ -53,8 +66,24 @@ pub fn execute(
             hub_address,
             token_limit,
             min_ntrn_ibc_fee,
+            hub_control,
+            timeout_bounds,
         ),
 
+        ExecuteMsg::RegisterOutpost {
+            address,
+            from_hub,
+            to_hub,
+            transport,
+            route,
+            chain_id,
+            timeout,
+        } => e::try_register_outpost(
+            deps, env, info, address, from_hub, to_hub, transport, route, chain_id, timeout,
+        ),
+
+        ExecuteMsg::RemoveOutpost { prefix } => e::try_remove_outpost(deps, env, info, prefix),
+
         ExecuteMsg::AddCollection {
             hub_collection,
             home_collection,
@@ -64,19 +93,93 @@ pub fn execute(
             e::try_remove_collection(deps, env, info, hub_collection)
         }
 
+        ExecuteMsg::CreateCollection {
+            home_collection,
+            name,
+            symbol,
+            flavor,
+        } => e::try_create_collection(deps, env, info, home_collection, name, symbol, flavor),
+
+        ExecuteMsg::UpdateCollection {
+            hub_collection,
+            token_limit,
+            is_paused,
+            fee,
+            daily_quota,
+            royalty_recipient,
+        } => e::try_update_collection(
+            deps,
+            env,
+            info,
+            hub_collection,
+            token_limit,
+            is_paused,
+            fee,
+            daily_quota,
+            royalty_recipient,
+        ),
+
         ExecuteMsg::SetChannel {
             prefix,
             from_hub,
             to_hub,
-        } => e::try_set_channel(deps, env, info, prefix, from_hub, to_hub),
+            transport,
+            route,
+            chain_id,
+            timeout,
+        } => e::try_set_channel(
+            deps, env, info, prefix, from_hub, to_hub, transport, route, chain_id, timeout,
+        ),
+
+        ExecuteMsg::RemoveChannel { prefix } => e::try_remove_channel(deps, env, info, prefix),
+
+        ExecuteMsg::SetCarrierDenoms { prefix, denoms } => {
+            e::try_set_carrier_denoms(deps, env, info, prefix, denoms)
+        }
+
+        ExecuteMsg::RotateKey { enc_key } => e::try_rotate_key(deps, env, info, enc_key),
 
         ExecuteMsg::Send {
             hub_collection,
             token_list,
             target,
-        } => e::try_send(deps, env, info, hub_collection, token_list, target),
+            timeout,
+        } => e::try_send(deps, env, info, hub_collection, token_list, target, timeout),
+
+        ExecuteMsg::Accept {
+            msg,
+            timestamp,
+            key_id,
+            nonce,
+        } => e::try_accept(deps, env, info, msg, timestamp, key_id, nonce),
+
+        ExecuteMsg::SendControl { prefix, action } => {
+            e::try_send_control(deps, env, info, prefix, action)
+        }
+
+        ExecuteMsg::Control {
+            msg,
+            timestamp,
+            key_id,
+            nonce,
+        } => e::try_control(deps, env, info, msg, timestamp, key_id, nonce),
 
-        ExecuteMsg::Accept { msg, timestamp } => e::try_accept(deps, env, info, msg, timestamp),
+        ExecuteMsg::ReceiveNfts {
+            hub_collection,
+            token_list,
+            metadata_list,
+            recipient,
+            processed_packet,
+        } => e::try_receive_nfts(
+            deps,
+            env,
+            info,
+            hub_collection,
+            token_list,
+            metadata_list,
+            recipient,
+            processed_packet,
+        ),
     }
 }
 
@@ -88,7 +191,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
         QueryMsg::PauseState {} => to_json_binary(&q::query_pause_state(deps, env)?),
 
-        QueryMsg::Outposts {} => to_json_binary(&q::query_outposts(deps, env)?),
+        QueryMsg::Outposts { start_after, limit } => {
+            to_json_binary(&q::query_outposts(deps, env, start_after, limit)?)
+        }
 
         QueryMsg::Collection {
             hub_collection,
@@ -100,9 +205,63 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             home_collection,
         )?),
 
-        QueryMsg::CollectionList {} => to_json_binary(&q::query_collection_list(deps, env)?),
+        QueryMsg::CollectionList { start_after, limit } => {
+            to_json_binary(&q::query_collection_list(deps, env, start_after, limit)?)
+        }
+
+        QueryMsg::ChannelList { start_after, limit } => {
+            to_json_binary(&q::query_channel_list(deps, env, start_after, limit)?)
+        }
+
+        QueryMsg::PacketState { sender, packet_id } => {
+            to_json_binary(&q::query_packet_state(deps, env, sender, packet_id)?)
+        }
+
+        QueryMsg::Transfer { id } => to_json_binary(&q::query_transfer(deps, env, id)?),
+
+        QueryMsg::TransfersBySender {
+            sender,
+            start_after,
+            limit,
+        } => to_json_binary(&q::query_transfers_by_sender(
+            deps,
+            env,
+            sender,
+            start_after,
+            limit,
+        )?),
+
+        QueryMsg::PendingTransfers { start_after, limit } => {
+            to_json_binary(&q::query_pending_transfers(deps, env, start_after, limit)?)
+        }
+
+        QueryMsg::CarrierRequirement { hub_collection } => {
+            to_json_binary(&q::query_carrier_requirement(deps, env, hub_collection)?)
+        }
+
+        QueryMsg::FeeBalance {} => to_json_binary(&q::query_fee_balance(deps, env)?),
 
-        QueryMsg::ChannelList {} => to_json_binary(&q::query_channel_list(deps, env)?),
+        QueryMsg::FeeEstimate {} => to_json_binary(&q::query_fee_estimate(deps, env)?),
+
+        QueryMsg::Control { id } => to_json_binary(&q::query_control(deps, env, id)?),
+
+        QueryMsg::ControlList { start_after, limit } => {
+            to_json_binary(&q::query_control_list(deps, env, start_after, limit)?)
+        }
+    }
+}
+
+/// Exposes all reply functions available in the contract
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
+    let Reply { id, result } = reply;
+
+    match id {
+        SAVE_IBC_SEQUENCE_REPLY => e::save_ibc_sequence(deps, env, &result),
+        IBC_RECEIVE_REPLY => i::ibc_receive_failed(deps, env, &result),
+        CREATE_COLLECTION_REPLY => e::save_hub_collection(deps, env, &result),
+        SAVE_CONTROL_SEQUENCE_REPLY => e::save_control_sequence(deps, env, &result),
+        _ => Err(ContractError::UndefinedReplyId),
     }
 }
 
@@ -114,7 +273,104 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
 
 /// Exposes all functions that can be called only by Cosmos SDK modules
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(_deps: DepsMut, _env: Env, _msg: SudoMsg) -> StdResult<Response> {
-    // needed for the fee refunder otherwise it throws an error
-    Ok(Response::new())
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        // neutron transfer callbacks
+        SudoMsg::Response { request, .. } => {
+            let (channel, sequence) = s::parse_request_packet(&request)?;
+            s::sudo_ack(deps, env, channel, sequence)
+        }
+
+        SudoMsg::Error { request, .. } => {
+            let (channel, sequence) = s::parse_request_packet(&request)?;
+            s::sudo_refund(deps, env, channel, sequence, false)
+        }
+
+        SudoMsg::Timeout { request } => {
+            let (channel, sequence) = s::parse_request_packet(&request)?;
+            s::sudo_refund(deps, env, channel, sequence, true)
+        }
+
+        // ibc-hooks callbacks
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel,
+            sequence,
+            success,
+            ..
+        }) => {
+            if success {
+                s::sudo_ack(deps, env, channel, sequence)
+            } else {
+                s::sudo_refund(deps, env, channel, sequence, false)
+            }
+        }
+
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout { channel, sequence }) => {
+            s::sudo_refund(deps, env, channel, sequence, true)
+        }
+
+        // needed for the fee refunder otherwise it throws an error
+        _ => Ok(Response::new()),
+    }
+}
+
+/// Validates ICS-721 or native transceiver channel handshake
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    i::ibc_open_channel(msg.channel(), msg.counterparty_version())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    i::ibc_connect_channel(deps, env, msg.channel(), msg.counterparty_version())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    i::ibc_close_channel(deps, env, msg.channel())
+}
+
+/// Receives ICS-721 and native packets, errors are returned to the sender as error ack
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    i::ibc_receive(deps, env, &msg.packet).or_else(|e| {
+        Ok(IbcReceiveResponse::new()
+            .set_ack(i::get_error_ack(&e.to_string()))
+            .add_attribute("action", "ibc_receive")
+            .add_attribute("error", e.to_string()))
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    i::ibc_ack(deps, env, &msg.original_packet, &msg.acknowledgement.data)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    i::ibc_timeout(deps, env, &msg.packet)
 }
@@ -1,15 +1,39 @@
 use cosmwasm_std::{
-    coins, to_json_string, Addr, Coin, CosmosMsg, StdResult, Storage, Timestamp, Uint128,
+    coin, coins, from_json, to_json_binary, to_json_string, Addr, Api, Attribute, BankMsg, Binary,
+    Coin, CosmosMsg, Decimal, Env, IbcPacket, MessageInfo, Order, QuerierWrapper, QueryRequest,
+    StdError, StdResult, Storage, Timestamp, Uint128, WasmMsg,
 };
 
-use anybuf::Anybuf;
+use encryption_helper::serde::serialize_encrypt;
+
+use anybuf::{Anybuf, Bufany};
+
+use serde::{de::IgnoredAny, Serialize};
 
 use snb_base::{
+    constants::NONCE_LEN,
+    converters::{get_addr_by_prefix, get_ibc_hooks_sender, get_nonce, get_pfm_sender},
     error::ContractError,
+    nft_minter::types::{
+        Cw2981CheckRoyaltiesResponse, Cw2981QueryMsg, Cw2981RoyaltiesInfoResponse, Metadata,
+        RoyaltyInfo, Sg721CollectionInfoResponse, Sg721QueryMsg, TokenMetadata,
+    },
+    private_communication::types::{EncryptedResponse, Hash},
     transceiver::{
-        state::{DENOM_NTRN, IS_PAUSED, PORT},
-        types::{Channel, IbcMemo},
+        msg::ExecuteMsg,
+        state::{
+            collections, transfers, CHANNELS, DAILY_USAGE, DENOM_NTRN, ENC_KEY, IBC_FEE_ESCROW,
+            IBC_TIMEOUT, IS_PAUSED, NONCE_COUNTER, OUTPOSTS, PFM_RECEIVER, PFM_RETRIES, PORT,
+            PREVIOUS_ENC_KEY, ROYALTY_SALE_PRICE, SECONDS_PER_DAY,
+        },
+        types::{
+            CarrierRequirement, Channel, Collection, Config, DailyUsage, EncKey,
+            FeeRefunderParamsResponse, ForwardMemo, Hop, IbcFee, IbcHooksMemo, IbcMemo, Ics721Memo,
+            NonFungibleTokenPacketData, Outpost, Packet, TransceiverType, Transfer, TransferStatus,
+            Transport, WasmMemo,
+        },
     },
+    utils::unwrap_field,
 };
 
 /// user actions are disabled when the contract is paused
@@ -69,11 +93,11 @@ pub fn get_neutron_ibc_transfer_msg(
     contract_address: &str,
     timeout_timestamp_ns: u64,
     ibc_transfer_memo: &str,
-    min_ntrn_ibc_fee: Uint128,
+    ibc_fee: &IbcFee,
 ) -> CosmosMsg {
     let recv_fee: &Vec<Coin> = &vec![];
-    let ack_fee = &coins(min_ntrn_ibc_fee.u128(), DENOM_NTRN);
-    let timeout_fee = &coins(min_ntrn_ibc_fee.u128(), DENOM_NTRN);
+    let ack_fee = &coins(ibc_fee.ack_fee.u128(), DENOM_NTRN);
+    let timeout_fee = &coins(ibc_fee.timeout_fee.u128(), DENOM_NTRN);
 
     // https://github.com/neutron-org/neutron-std/blob/main/packages/neutron-std/src/types/neutron/transfer.rs
     // https://github.com/neutron-org/neutron/blob/main/proto/neutron/transfer/v1/tx.proto#L25
@@ -125,48 +149,1018 @@ fn get_coin_msgs(coin_list: &[Coin]) -> Vec<Anybuf> {
         .collect()
 }
 
+/// returns the current key or the previous one if it isn't expired yet
+pub fn get_enc_key(storage: &dyn Storage, block_time: &Timestamp, key_id: u64) -> StdResult<Hash> {
+    let enc_key = ENC_KEY
+        .may_load(storage)?
+        .ok_or(ContractError::EncKeyIsNotFound)?;
+
+    if enc_key.id == key_id {
+        return Hash::parse(&enc_key.value);
+    }
+
+    let previous_enc_key = PREVIOUS_ENC_KEY
+        .may_load(storage)?
+        .filter(|x| x.id == key_id)
+        .ok_or(ContractError::EncKeyIsNotFound)?;
+
+    if previous_enc_key
+        .expiration
+        .map_or(true, |x| block_time >= &x)
+    {
+        Err(ContractError::EncKeyIsExpired)?;
+    }
+
+    Hash::parse(&previous_enc_key.value)
+}
+
+/// increments the counter to get unique nonce for each encrypted message
+pub fn get_next_nonce(storage: &mut dyn Storage, env: &Env) -> StdResult<[u8; NONCE_LEN]> {
+    let counter = NONCE_COUNTER.update(storage, |x| -> StdResult<_> { Ok(x + 1) })?;
+
+    Ok(get_nonce(env.contract.address.as_str(), counter))
+}
+
+pub fn get_accept_msg(encrypted_response: &EncryptedResponse) -> ExecuteMsg {
+    let EncryptedResponse {
+        value,
+        timestamp,
+        key_id,
+        nonce,
+    } = encrypted_response.to_owned();
+
+    ExecuteMsg::Accept {
+        msg: value,
+        timestamp,
+        key_id,
+        nonce,
+    }
+}
+
+pub fn get_control_msg(encrypted_response: &EncryptedResponse) -> ExecuteMsg {
+    let EncryptedResponse {
+        value,
+        timestamp,
+        key_id,
+        nonce,
+    } = encrypted_response.to_owned();
+
+    ExecuteMsg::Control {
+        msg: value,
+        timestamp,
+        key_id,
+        nonce,
+    }
+}
+
+/// encrypts the packet with the current key and unique nonce
+pub fn get_encrypted_packet(
+    storage: &mut dyn Storage,
+    env: &Env,
+    packet: &impl Serialize,
+) -> StdResult<EncryptedResponse> {
+    let EncKey { id, value, .. } = ENC_KEY.load(storage)?;
+    let nonce = get_next_nonce(storage, env)?;
+
+    serialize_encrypt(&Hash::parse(&value)?, id, &nonce, &env.block.time, packet)
+}
+
+/// ibc_callback must be specified to receive ack/timeout from ibc-hooks,
+/// forward memos are nested for each intermediate chain of multi-hop route
 pub fn get_ibc_transfer_memo(
     contract_address: &str,
-    msg: &str,
-    timestamp: Timestamp,
+    encrypted_response: &EncryptedResponse,
+    ibc_callback: Option<&Addr>,
+    forward_channels: &[String],
+    timeout: u64,
 ) -> StdResult<String> {
-    to_json_string(&IbcMemo::Wasm {
-        contract: contract_address.to_string(),
-        msg: snb_base::transceiver::msg::ExecuteMsg::Accept {
-            msg: msg.to_string(),
-            timestamp,
-        },
+    get_ibc_hooks_memo(
+        contract_address,
+        get_accept_msg(encrypted_response),
+        ibc_callback,
+        forward_channels,
+        timeout,
+    )
+}
+
+/// memo executing the msg on the target contract, forward hops use the same relative timeout
+/// in seconds as the first transfer
+pub fn get_ibc_hooks_memo(
+    contract_address: &str,
+    msg: ExecuteMsg,
+    ibc_callback: Option<&Addr>,
+    forward_channels: &[String],
+    timeout: u64,
+) -> StdResult<String> {
+    let contract = contract_address.to_string();
+
+    let mut memo = IbcMemo::Wasm {
+        contract: contract.clone(),
+        msg,
+    };
+
+    for (i, channel) in forward_channels.iter().enumerate().rev() {
+        // the last hop sends the transfer to the target contract
+        let receiver = if i + 1 == forward_channels.len() {
+            contract.clone()
+        } else {
+            PFM_RECEIVER.to_string()
+        };
+
+        memo = IbcMemo::Forward(ForwardMemo {
+            receiver,
+            port: PORT.to_string(),
+            channel: channel.to_owned(),
+            retries: PFM_RETRIES,
+            timeout: timeout * 1_000_000_000,
+            next: Some(Box::new(memo)),
+        });
+    }
+
+    match (ibc_callback, memo) {
+        (Some(x), IbcMemo::Wasm { contract, msg }) => to_json_string(&IbcHooksMemo {
+            wasm: Some(WasmMemo { contract, msg }),
+            forward: None,
+            ibc_callback: x.to_string(),
+        }),
+        (Some(x), IbcMemo::Forward(forward)) => to_json_string(&IbcHooksMemo {
+            wasm: None,
+            forward: Some(forward),
+            ibc_callback: x.to_string(),
+        }),
+        (None, memo) => to_json_string(&memo),
+    }
+}
+
+/// parses sequence from MsgTransferResponse, it's first field both for ibc-go and neutron
+pub fn parse_ibc_sequence(data: &Option<Binary>) -> StdResult<u64> {
+    let data = unwrap_field(data.to_owned(), "data")?;
+    let response = Bufany::deserialize(&data).map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok(unwrap_field(response.uint64(1), "sequence")?)
+}
+
+/// returns nfts and carrier coin to the sender of failed transfer
+pub fn get_refund_msgs(config: &Config, transfer: &Transfer) -> StdResult<Vec<CosmosMsg>> {
+    let Transfer {
+        sender,
+        hub_collection,
+        home_collection,
+        token_list,
+        metadata_list,
+        carrier,
+        fee,
+        ..
+    } = transfer;
+
+    let mut msg_list = match config.transceiver_type {
+        // burned nfts must be minted again
+        TransceiverType::Hub => vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.nft_minter.clone(),
+            msg: to_json_binary(&snb_base::nft_minter::msg::ExecuteMsg::Mint {
+                collection: hub_collection.to_owned(),
+                token_list: token_list.to_owned(),
+                recipient: sender.to_string(),
+                metadata_list: Some(metadata_list.to_owned()),
+            })?,
+            funds: vec![],
+        })],
+        // locked nfts must be unlocked
+        TransceiverType::Outpost => token_list
+            .iter()
+            .map(|token_id| {
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: home_collection.to_owned(),
+                    msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                        recipient: sender.to_string(),
+                        token_id: token_id.to_string(),
+                    })?,
+                    funds: vec![],
+                }))
+            })
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    for x in [carrier, fee].into_iter().flatten() {
+        msg_list.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![x.to_owned()],
+        }));
+    }
+
+    Ok(msg_list)
+}
+
+/// sends escrowed collection fee of delivered transfer to the admin
+pub fn release_collection_fee(config: &Config, transfer: &Transfer) -> Vec<CosmosMsg> {
+    transfer
+        .fee
+        .iter()
+        .map(|x| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: config.admin.to_string(),
+                amount: vec![x.to_owned()],
+            })
+        })
+        .collect()
+}
+
+pub fn set_transfer_status(
+    storage: &mut dyn Storage,
+    id: u64,
+    status: TransferStatus,
+) -> StdResult<Transfer> {
+    transfers().update(storage, id, |x| -> StdResult<_> {
+        let mut transfer = x.ok_or(ContractError::TransferIsNotFound)?;
+        transfer.status = status;
+
+        Ok(transfer)
+    })
+}
+
+pub fn get_collection_by_home(
+    storage: &dyn Storage,
+    home_collection: &str,
+) -> StdResult<Collection> {
+    collections()
+        .idx
+        .home
+        .item(storage, home_collection.to_string())?
+        .map(|(_, x)| x)
+        .ok_or(ContractError::CollectionIsNotFound.into())
+}
+
+/// native packet contains transfer id, ics721 packet contains it in the memo.
+/// returns None for packets not sent by the contract or already processed
+pub fn find_pending_transfer(
+    storage: &dyn Storage,
+    packet: &IbcPacket,
+) -> StdResult<Option<Transfer>> {
+    let id = match from_json::<Packet>(&packet.data) {
+        Ok(x) => Some(x.id),
+        Err(_) => from_json::<NonFungibleTokenPacketData>(&packet.data)
+            .ok()
+            .and_then(|x| x.memo)
+            .and_then(|x| from_json::<Ics721Memo>(x.as_bytes()).ok())
+            .map(|x| x.transfer_id),
+    };
+
+    let transfer = match id {
+        Some(x) => transfers().may_load(storage, x)?,
+        None => None,
+    };
+
+    Ok(transfer.filter(|x| {
+        x.status == TransferStatus::Pending && x.channel.as_ref() == Some(&packet.src.channel_id)
+    }))
+}
+
+/// hub mints received nfts, outpost unlocks them
+/// returns msgs and attributes of receiving nfts
+pub fn get_receive_msgs(
+    config: &Config,
+    collection: &Collection,
+    token_list: &[String],
+    metadata_list: &[TokenMetadata],
+    recipient: impl ToString,
+) -> StdResult<(Vec<CosmosMsg>, Vec<Attribute>)> {
+    if collection.is_paused {
+        Err(ContractError::CollectionIsPaused)?;
+    }
+
+    match config.transceiver_type {
+        TransceiverType::Hub => {
+            // home chain payment address is converted to the hub prefix, the account configured
+            // by the admin overrides it, royalty is dropped if the address can't be converted
+            let (hub_prefix, _) = split_address(&collection.hub_collection);
+            let mut attributes = vec![];
+            let metadata_list = metadata_list
+                .iter()
+                .cloned()
+                .map(|x| TokenMetadata {
+                    royalty_info: x.royalty_info.and_then(|royalty_info| {
+                        let payment_address = collection.royalty_recipient.clone().or_else(|| {
+                            get_addr_by_prefix(&royalty_info.payment_address, &hub_prefix).ok()
+                        });
+
+                        match payment_address {
+                            Some(payment_address) => Some(RoyaltyInfo {
+                                payment_address,
+                                ..royalty_info
+                            }),
+                            None => {
+                                attributes = vec![Attribute::new("royalty", "dropped")];
+                                None
+                            }
+                        }
+                    }),
+                    ..x
+                })
+                .collect();
+
+            let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.nft_minter.clone(),
+                msg: to_json_binary(&snb_base::nft_minter::msg::ExecuteMsg::Mint {
+                    collection: collection.hub_collection.to_owned(),
+                    token_list: token_list.to_owned(),
+                    recipient: recipient.to_string(),
+                    metadata_list: Some(metadata_list),
+                })?,
+                funds: vec![],
+            });
+
+            Ok((vec![msg], attributes))
+        }
+        TransceiverType::Outpost => {
+            let msg_list = token_list
+                .iter()
+                .map(|token_id| {
+                    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: collection.home_collection.to_owned(),
+                        msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                            recipient: recipient.to_string(),
+                            token_id: token_id.to_string(),
+                        })?,
+                        funds: vec![],
+                    }))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            Ok((msg_list, vec![]))
+        }
+    }
+}
+
+/// returns token_uri, extension and royalty of each token or empty list if the collection has
+/// no metadata, extension is skipped if it doesn't match the standard on-chain metadata
+pub fn get_token_metadata(
+    querier: QuerierWrapper,
+    collection: &str,
+    token_list: &[String],
+) -> Vec<TokenMetadata> {
+    let is_cw2981 = querier
+        .query_wasm_smart::<Cw2981CheckRoyaltiesResponse>(
+            collection,
+            &cw721_base::QueryMsg::Extension {
+                msg: Cw2981QueryMsg::CheckRoyalties {},
+            },
+        )
+        .map(|x| x.royalty_payments)
+        .unwrap_or_default();
+
+    let metadata_list = token_list
+        .iter()
+        .map(|token_id| {
+            let msg = cw721::Cw721QueryMsg::NftInfo {
+                token_id: token_id.to_string(),
+            };
+
+            querier
+                .query_wasm_smart::<cw721::NftInfoResponse<Option<Metadata>>>(collection, &msg)
+                .map(|x| TokenMetadata {
+                    token_uri: x.token_uri,
+                    extension: x.extension.filter(|x| x != &Metadata::default()),
+                    royalty_info: None,
+                })
+                .or_else(|_| {
+                    querier
+                        .query_wasm_smart::<cw721::NftInfoResponse<IgnoredAny>>(collection, &msg)
+                        .map(|x| TokenMetadata {
+                            token_uri: x.token_uri,
+                            extension: None,
+                            royalty_info: None,
+                        })
+                })
+                .map(|x| TokenMetadata {
+                    royalty_info: if is_cw2981 {
+                        get_token_royalty(querier, collection, token_id)
+                    } else {
+                        None
+                    },
+                    ..x
+                })
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    if metadata_list.iter().all(|x| x == &TokenMetadata::default()) {
+        return vec![];
+    }
+
+    metadata_list
+}
+
+/// cw2981 royalty is calculated for the reference sale price, zero royalty is skipped
+fn get_token_royalty(
+    querier: QuerierWrapper,
+    collection: &str,
+    token_id: &str,
+) -> Option<RoyaltyInfo> {
+    let sale_price = Uint128::new(ROYALTY_SALE_PRICE);
+
+    querier
+        .query_wasm_smart::<Cw2981RoyaltiesInfoResponse>(
+            collection,
+            &cw721_base::QueryMsg::Extension {
+                msg: Cw2981QueryMsg::RoyaltyInfo {
+                    token_id: token_id.to_string(),
+                    sale_price,
+                },
+            },
+        )
+        .ok()
+        .filter(|x| !x.royalty_amount.is_zero() && !x.address.is_empty())
+        .map(|x| RoyaltyInfo {
+            payment_address: x.address,
+            share: Decimal::from_ratio(x.royalty_amount, sale_price),
+        })
+}
+
+/// returns royalty of sg721 collection, other collections have no collection royalty
+pub fn get_collection_royalty(querier: QuerierWrapper, collection: &str) -> Option<RoyaltyInfo> {
+    querier
+        .query_wasm_smart::<Sg721CollectionInfoResponse>(
+            collection,
+            &Sg721QueryMsg::CollectionInfo {},
+        )
+        .ok()
+        .and_then(|x| x.royalty_info)
+}
+
+/// collection royalty is applied to tokens without their own royalty
+pub fn apply_collection_royalty(
+    metadata_list: Vec<TokenMetadata>,
+    token_amount: usize,
+    royalty_info: Option<RoyaltyInfo>,
+) -> Vec<TokenMetadata> {
+    let Some(royalty_info) = royalty_info else {
+        return metadata_list;
+    };
+
+    let metadata_list = if metadata_list.is_empty() {
+        vec![TokenMetadata::default(); token_amount]
+    } else {
+        metadata_list
+    };
+
+    metadata_list
+        .into_iter()
+        .map(|x| TokenMetadata {
+            royalty_info: x.royalty_info.or(Some(royalty_info.clone())),
+            ..x
+        })
+        .collect()
+}
+
+/// returns (token_uris, token_data) of ics721 packet, token data is json encoded extension
+pub fn get_ics721_token_metadata(
+    metadata_list: &[TokenMetadata],
+) -> StdResult<Option<(Vec<String>, Vec<Binary>)>> {
+    if metadata_list.is_empty() {
+        return Ok(None);
+    }
+
+    let token_uris = metadata_list
+        .iter()
+        .map(|x| x.token_uri.to_owned().unwrap_or_default())
+        .collect();
+    let token_data = metadata_list
+        .iter()
+        .map(|x| match &x.extension {
+            Some(extension) => to_json_binary(extension),
+            None => Ok(Binary::default()),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Some((token_uris, token_data)))
+}
+
+/// inverse of get_ics721_token_metadata, unknown token data is skipped
+pub fn parse_ics721_token_metadata(
+    token_amount: usize,
+    token_uris: Option<Vec<String>>,
+    token_data: Option<Vec<Binary>>,
+) -> Vec<TokenMetadata> {
+    let token_uris = token_uris.unwrap_or_default();
+    let token_data = token_data.unwrap_or_default();
+
+    if token_uris.is_empty() && token_data.is_empty() {
+        return vec![];
+    }
+
+    (0..token_amount)
+        .map(|i| TokenMetadata {
+            token_uri: token_uris.get(i).filter(|x| !x.is_empty()).cloned(),
+            extension: token_data.get(i).and_then(|x| from_json(x).ok()),
+            royalty_info: None,
+        })
+        .collect()
+}
+
+/// returns true if any parameter is updated
+pub fn update_config(
+    api: &dyn Api,
+    config: &mut Config,
+    nft_minter: Option<String>,
+    hub_address: Option<String>,
+    token_limit: Option<u8>,
+    min_ntrn_ibc_fee: Option<Uint128>,
+) -> Result<bool, ContractError> {
+    let mut is_config_updated = false;
+
+    if let Some(x) = nft_minter {
+        if config.transceiver_type == TransceiverType::Hub {
+            api.addr_validate(&x)?;
+        }
+
+        config.nft_minter = x;
+        is_config_updated = true;
+    }
+
+    if let Some(x) = hub_address {
+        if config.transceiver_type == TransceiverType::Hub {
+            Err(ContractError::WrongActionType)?;
+        }
+
+        config.hub_address = x;
+        is_config_updated = true;
+    }
+
+    if let Some(x) = token_limit {
+        config.token_limit = x;
+        is_config_updated = true;
+    }
+
+    if let Some(x) = min_ntrn_ibc_fee {
+        config.min_ntrn_ibc_fee = x;
+        is_config_updated = true;
+    }
+
+    Ok(is_config_updated)
+}
+
+pub fn add_collection(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    config: &Config,
+    hub_collection: &str,
+    home_collection: &str,
+) -> Result<(), ContractError> {
+    if collections().has(storage, hub_collection)
+        || collections()
+            .idx
+            .home
+            .item(storage, home_collection.to_string())?
+            .is_some()
+    {
+        Err(ContractError::CollectionDuplication)?;
+    }
+
+    if config.transceiver_type == TransceiverType::Hub {
+        api.addr_validate(hub_collection)?;
+    } else {
+        api.addr_validate(home_collection)?;
+    }
+
+    collections().save(
+        storage,
+        hub_collection,
+        &Collection::new(hub_collection, home_collection),
+    )?;
+
+    Ok(())
+}
+
+pub fn remove_collection(storage: &mut dyn Storage, hub_collection: &str) -> StdResult<()> {
+    collections().remove(storage, hub_collection)?;
+    DAILY_USAGE.remove(storage, hub_collection);
+
+    Ok(())
+}
+
+/// returns funds left after paying the collection fee
+pub fn split_collection_fee(
+    info: &MessageInfo,
+    fee: &Option<Coin>,
+) -> Result<MessageInfo, ContractError> {
+    let fee = match fee {
+        Some(x) => x,
+        None => return Ok(info.to_owned()),
+    };
+
+    let mut funds: Vec<Coin> = vec![];
+    let mut is_fee_paid = false;
+
+    for x in &info.funds {
+        if x.denom != fee.denom {
+            funds.push(x.to_owned());
+            continue;
+        }
+
+        let amount = x
+            .amount
+            .checked_sub(fee.amount)
+            .map_err(|_| ContractError::WrongFundsCombination)?;
+        is_fee_paid = true;
+
+        if !amount.is_zero() {
+            funds.push(coin(amount.u128(), &x.denom));
+        }
+    }
+
+    if !is_fee_paid {
+        Err(ContractError::WrongFundsCombination)?;
+    }
+
+    Ok(MessageInfo {
+        sender: info.sender.to_owned(),
+        funds,
     })
 }
 
-/// returns (ibc_channel, target_transceiver)
+/// adds sent tokens to the collection usage for the current day
+pub fn update_daily_usage(
+    storage: &mut dyn Storage,
+    block_time: &Timestamp,
+    collection: &Collection,
+    token_amount: usize,
+) -> Result<(), ContractError> {
+    let daily_quota = match collection.daily_quota {
+        Some(x) => x,
+        None => return Ok(()),
+    };
+
+    let day = block_time.seconds() / SECONDS_PER_DAY;
+
+    DAILY_USAGE.update(storage, &collection.hub_collection, |x| {
+        let sent_today = x.filter(|y| y.day == day).map_or(0, |y| y.token_amount);
+        let token_amount = sent_today + token_amount as u32;
+
+        if token_amount > daily_quota {
+            Err(ContractError::ExceededDailyQuota)?;
+        }
+
+        Ok::<_, ContractError>(DailyUsage { day, token_amount })
+    })?;
+
+    Ok(())
+}
+
+/// removes refunded tokens from the collection usage if they were sent on the current usage day
+pub fn revert_daily_usage(storage: &mut dyn Storage, transfer: &Transfer) -> StdResult<()> {
+    let day = transfer.timestamp.seconds() / SECONDS_PER_DAY;
+    let usage = match DAILY_USAGE.may_load(storage, &transfer.hub_collection)? {
+        Some(x) if x.day == day => x,
+        _ => return Ok(()),
+    };
+
+    DAILY_USAGE.save(
+        storage,
+        &transfer.hub_collection,
+        &DailyUsage {
+            day,
+            token_amount: usage
+                .token_amount
+                .saturating_sub(transfer.token_list.len() as u32),
+        },
+    )
+}
+
+/// returns (ibc_channel, channel, target_transceiver)
 pub fn get_channel_and_transceiver(
-    contract_address: &Addr,
-    hub_address: &str,
+    storage: &dyn Storage,
+    config: &Config,
     home_collection: &str,
-    outpost_list: &[String],
-    channel_list: &[Channel],
-) -> StdResult<(String, String)> {
-    let is_hub_sender = contract_address.as_str() == hub_address;
+) -> StdResult<(String, Channel, String)> {
     let (home_prefix, _) = split_address(home_collection);
-    let channel = channel_list
-        .iter()
-        .find(|x| x.prefix == home_prefix)
-        .ok_or(ContractError::ChannelIsNotFound)?;
 
-    if is_hub_sender {
-        let outpost = outpost_list
+    match config.transceiver_type {
+        TransceiverType::Hub => {
+            let Outpost {
+                address, channel, ..
+            } = OUTPOSTS
+                .may_load(storage, &home_prefix)?
+                .ok_or(ContractError::OutpostIsNotFound)?;
+            let channel = channel.ok_or(ContractError::ChannelIsNotFound)?;
+
+            Ok((channel.from_hub.clone(), channel, address))
+        }
+        TransceiverType::Outpost => {
+            let channel = CHANNELS
+                .may_load(storage, &home_prefix)?
+                .ok_or(ContractError::ChannelIsNotFound)?;
+
+            Ok((
+                channel.to_hub.clone(),
+                channel,
+                config.hub_address.to_owned(),
+            ))
+        }
+    }
+}
+
+pub fn get_channel(
+    prefix: &str,
+    from_hub: &str,
+    to_hub: &str,
+    transport: Option<Transport>,
+    route: Option<Vec<Hop>>,
+    chain_id: Option<String>,
+    timeout: Option<u64>,
+) -> StdResult<Channel> {
+    let transport = transport.unwrap_or_default();
+    let route = route.unwrap_or_default();
+
+    // packet-forward-middleware can forward only ics20 transfers
+    if !route.is_empty() && transport != Transport::Ics20 {
+        Err(ContractError::RouteIsNotSupported)?;
+    }
+
+    for channel_id in [from_hub, to_hub].into_iter().chain(
+        route
             .iter()
-            .find(|x| {
-                let (prefix, _) = split_address(x);
-                prefix == home_prefix
-            })
-            .ok_or(ContractError::OutpostIsNotFound)?;
+            .flat_map(|x| [x.from_hub.as_str(), x.to_hub.as_str()]),
+    ) {
+        validate_channel_id(channel_id)?;
+    }
+
+    Ok(Channel::new(prefix, from_hub, to_hub)
+        .with_transport(transport)
+        .with_route(route)
+        .with_chain_id(chain_id)
+        .with_timeout(timeout))
+}
+
+/// returns relative ibc timeout in seconds, timeout specified by the sender or the channel
+/// is clamped to the config bounds
+pub fn get_ibc_timeout(config: &Config, channel: &Channel, timeout: Option<u64>) -> u64 {
+    let timeout = timeout.or(channel.timeout).unwrap_or(IBC_TIMEOUT);
+
+    config.timeout_bounds.clamp(timeout)
+}
+
+/// min ack and timeout fees are read from neutron fee refunder params,
+/// the configured minimum is used if the params are unavailable
+pub fn get_ibc_fee(querier: QuerierWrapper, config: &Config) -> IbcFee {
+    let min_fee = querier
+        .query::<FeeRefunderParamsResponse>(&QueryRequest::Stargate {
+            path: "/neutron.feerefunder.Query/Params".to_string(),
+            data: Binary::default(),
+        })
+        .map(|x| x.params.min_fee)
+        .ok();
+
+    let get_amount = |fee: Option<&Vec<Coin>>| {
+        fee.and_then(|x| x.iter().find(|x| x.denom == DENOM_NTRN))
+            .map(|x| x.amount)
+            .unwrap_or(config.min_ntrn_ibc_fee)
+    };
+
+    IbcFee {
+        ack_fee: get_amount(min_fee.as_ref().map(|x| &x.ack_fee)),
+        timeout_fee: get_amount(min_fee.as_ref().map(|x| &x.timeout_fee)),
+    }
+}
+
+/// relayer gets the ack fee on ack and the timeout fee on timeout,
+/// the other one is returned to the sender
+pub fn release_ibc_fee(
+    storage: &mut dyn Storage,
+    ibc_fee: &Option<IbcFee>,
+    sender: &str,
+    is_timeout: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    let ibc_fee = match ibc_fee {
+        Some(x) => x,
+        None => return Ok(vec![]),
+    };
 
-        Ok((channel.from_hub.clone(), outpost.to_owned()))
+    IBC_FEE_ESCROW.update(storage, |x| -> StdResult<_> {
+        Ok(x.saturating_sub(ibc_fee.total()))
+    })?;
+
+    let unused_fee = if is_timeout {
+        ibc_fee.ack_fee
     } else {
-        Ok((channel.to_hub.clone(), hub_address.to_owned()))
+        ibc_fee.timeout_fee
+    };
+
+    if unused_fee.is_zero() {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![CosmosMsg::Bank(BankMsg::Send {
+        to_address: sender.to_string(),
+        amount: coins(unused_fee.u128(), DENOM_NTRN),
+    })])
+}
+
+/// route isn't specified for transfers on the same chain, they require a carrier of any denom.
+/// packets sent over the contract's own channel don't require a carrier, ibc transfer from
+/// the hub requires untrn to pay ibc fees
+pub fn get_carrier_requirement(
+    querier: QuerierWrapper,
+    config: &Config,
+    route: Option<&Channel>,
+) -> CarrierRequirement {
+    let amount_in = Uint128::one();
+
+    match route {
+        None => CarrierRequirement {
+            denoms: vec![],
+            amount: amount_in,
+        },
+        Some(channel) if channel.transport != Transport::Ics20 => CarrierRequirement {
+            denoms: vec![],
+            amount: Uint128::zero(),
+        },
+        Some(channel) => match config.transceiver_type {
+            TransceiverType::Hub => CarrierRequirement {
+                denoms: vec![DENOM_NTRN.to_string()],
+                amount: amount_in + get_ibc_fee(querier, config).total(),
+            },
+            TransceiverType::Outpost => CarrierRequirement {
+                denoms: channel.carrier_denoms.clone(),
+                amount: amount_in,
+            },
+        },
+    }
+}
+
+/// channel id must be in format channel-N
+pub fn validate_channel_id(channel_id: &str) -> StdResult<()> {
+    let is_valid = channel_id.strip_prefix("channel-").map_or(false, |x| {
+        !x.is_empty() && x.chars().all(|c| c.is_ascii_digit())
+    });
+
+    if !is_valid {
+        Err(ContractError::WrongChannelId)?;
     }
+
+    Ok(())
+}
+
+/// returns (receive_channel, forward_channel, prefix) of intermediate chains
+/// ordered in direction of the transfer
+fn get_hops(channel: &Channel, is_from_hub: bool) -> Vec<(String, String, String)> {
+    let hop_list = channel.route.iter().map(|x| {
+        if is_from_hub {
+            (
+                x.to_hub.to_owned(),
+                x.from_hub.to_owned(),
+                x.prefix.to_owned(),
+            )
+        } else {
+            (
+                x.from_hub.to_owned(),
+                x.to_hub.to_owned(),
+                x.prefix.to_owned(),
+            )
+        }
+    });
+
+    if is_from_hub {
+        hop_list.collect()
+    } else {
+        hop_list.rev().collect()
+    }
+}
+
+/// returns channels used by packet-forward-middleware to send the transfer to the target
+pub fn get_forward_channels(config: &Config, channel: &Channel) -> Vec<String> {
+    let is_from_hub = config.transceiver_type == TransceiverType::Hub;
+
+    get_hops(channel, is_from_hub)
+        .into_iter()
+        .map(|(_, forward_channel, _)| forward_channel)
+        .collect()
+}
+
+/// returns the address sending the transfer to the contract on the last hop of the route
+pub fn get_route_sender(
+    config: &Config,
+    channel: &Channel,
+    original_sender: &str,
+) -> StdResult<String> {
+    let is_from_hub = config.transceiver_type == TransceiverType::Outpost;
+
+    get_hops(channel, is_from_hub).into_iter().try_fold(
+        original_sender.to_string(),
+        |sender, (receive_channel, _, prefix)| get_pfm_sender(&receive_channel, &sender, &prefix),
+    )
+}
+
+/// nfts are sent back to the source network with the hub prefix in the class id,
+/// the outpost is the source network so it sends the base class id
+pub fn get_ics721_class_id(
+    config: &Config,
+    contract_address: &Addr,
+    ibc_channel: &str,
+    home_collection: &str,
+) -> String {
+    match config.transceiver_type {
+        TransceiverType::Hub => format!(
+            "{}/{}/{}",
+            get_ibc_port(contract_address),
+            ibc_channel,
+            home_collection
+        ),
+        TransceiverType::Outpost => home_collection.to_string(),
+    }
+}
+
+pub fn get_ibc_port(contract_address: impl ToString) -> String {
+    format!("wasm.{}", contract_address.to_string())
+}
+
+/// returns (channel, counterparty_transceiver) registered for packet transport
+/// with the same local channel id
+pub fn get_ibc_channel(
+    storage: &dyn Storage,
+    contract_address: &Addr,
+    config: &Config,
+    ibc_channel: &str,
+) -> StdResult<(Channel, String)> {
+    let (own_prefix, _) = split_address(contract_address);
+
+    let channel = match config.transceiver_type {
+        TransceiverType::Hub => OUTPOSTS
+            .range(storage, None, None, Order::Ascending)
+            .filter_map(|x| x.ok().map(|(_, outpost)| outpost))
+            .find_map(|x| match x.channel {
+                Some(channel) if channel.from_hub == ibc_channel => Some((channel, x.address)),
+                _ => None,
+            }),
+        TransceiverType::Outpost => CHANNELS
+            .may_load(storage, &own_prefix)?
+            .filter(|x| x.to_hub == ibc_channel)
+            .map(|x| (x, config.hub_address.to_owned())),
+    };
+
+    match channel {
+        Some((x, _)) if x.transport == Transport::Ics20 => Err(ContractError::ChannelIsNotFound)?,
+        Some(x) => Ok(x),
+        None => Err(ContractError::ChannelIsNotFound)?,
+    }
+}
+
+/// Accept can be executed only by ibc-hooks on behalf of counterparty transceiver
+/// or directly by registered transceiver on the same chain
+pub fn check_accept_sender(
+    storage: &dyn Storage,
+    contract_address: &Addr,
+    accept_sender: &Addr,
+    config: &Config,
+    packet_sender: &str,
+) -> StdResult<()> {
+    let (own_prefix, _) = split_address(contract_address);
+    let (sender_prefix, _) = split_address(packet_sender);
+
+    let channel = match config.transceiver_type {
+        TransceiverType::Hub => {
+            let outpost = OUTPOSTS
+                .may_load(storage, &sender_prefix)?
+                .ok_or(ContractError::OutpostIsNotFound)?;
+
+            if outpost.address != packet_sender {
+                Err(ContractError::Unauthorized)?;
+            }
+
+            outpost.channel
+        }
+        TransceiverType::Outpost => {
+            if packet_sender != config.hub_address {
+                Err(ContractError::Unauthorized)?;
+            }
+
+            CHANNELS.may_load(storage, &own_prefix)?
+        }
+    };
+
+    // same network
+    if accept_sender.as_str() == packet_sender {
+        if config.transceiver_type == TransceiverType::Hub && channel.is_some() {
+            Err(ContractError::Unauthorized)?;
+        }
+
+        return Ok(());
+    }
+
+    // ibc transfer is received on the channel of the outpost network
+    let channel = channel.ok_or(ContractError::ChannelIsNotFound)?;
+    let destination_channel = match config.transceiver_type {
+        TransceiverType::Hub => &channel.from_hub,
+        TransceiverType::Outpost => &channel.to_hub,
+    };
+    // multi-hop transfer is delivered by packet-forward-middleware of the last hop
+    let route_sender = get_route_sender(config, &channel, packet_sender)?;
+    let ibc_hooks_sender = get_ibc_hooks_sender(destination_channel, &route_sender, &own_prefix)?;
+
+    if accept_sender.as_str() != ibc_hooks_sender {
+        Err(ContractError::Unauthorized)?;
+    }
+
+    Ok(())
 }
 
 pub fn split_address(address: impl ToString) -> (String, String) {
@@ -3,7 +3,9 @@ pub mod helpers;
 
 pub mod actions {
     pub mod execute;
+    pub mod ibc;
     pub mod instantiate;
     pub mod migrate;
     pub mod query;
+    pub mod sudo;
 }
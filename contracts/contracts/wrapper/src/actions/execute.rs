@@ -334,6 +334,7 @@ fn get_mint_nft_msg(
             collection: collection.to_string(),
             token_list: token_list.to_owned(),
             recipient: recipient.to_string(),
+            metadata_list: None,
         })?,
         funds: vec![],
     }))
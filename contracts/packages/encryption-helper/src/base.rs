@@ -6,7 +6,7 @@ use aes_gcm_siv::{
 };
 
 use snb_base::{
-    constants::ENC_KEY_LEN,
+    constants::{ENC_KEY_LEN, NONCE_LEN},
     converters::{str_to_u8_vec, u8_vec_to_str, utf8_vec_to_str},
 };
 
@@ -16,8 +16,20 @@ fn get_cipher(enc_key: &[u8; ENC_KEY_LEN]) -> StdResult<Aes256GcmSiv> {
     Ok(Aes256GcmSiv::new(generic_array))
 }
 
-pub fn encrypt(msg: &str, enc_key: &[u8; ENC_KEY_LEN], nonce: &str) -> StdResult<String> {
-    let nonce: &GenericArray<u8, _> = Nonce::from_slice(nonce.as_bytes());
+fn get_nonce(nonce: &[u8]) -> StdResult<&Nonce> {
+    if nonce.len() != NONCE_LEN {
+        Err(StdError::generic_err(format!(
+            "Nonce length is {} but expected {}",
+            nonce.len(),
+            NONCE_LEN
+        )))?;
+    }
+
+    Ok(Nonce::from_slice(nonce))
+}
+
+pub fn encrypt(msg: &str, enc_key: &[u8; ENC_KEY_LEN], nonce: &[u8]) -> StdResult<String> {
+    let nonce = get_nonce(nonce)?;
     let cipher = get_cipher(enc_key)?;
 
     cipher
@@ -26,8 +38,8 @@ pub fn encrypt(msg: &str, enc_key: &[u8; ENC_KEY_LEN], nonce: &str) -> StdResult
         .map(|bytes| u8_vec_to_str(&bytes))
 }
 
-pub fn decrypt(enc_msg: &str, enc_key: &[u8; ENC_KEY_LEN], nonce: &str) -> StdResult<String> {
-    let nonce: &GenericArray<u8, _> = Nonce::from_slice(nonce.as_bytes());
+pub fn decrypt(enc_msg: &str, enc_key: &[u8; ENC_KEY_LEN], nonce: &[u8]) -> StdResult<String> {
+    let nonce = get_nonce(nonce)?;
     let cipher = get_cipher(enc_key)?;
 
     cipher
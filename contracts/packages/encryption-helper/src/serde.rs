@@ -1,9 +1,9 @@
-use cosmwasm_std::{from_json, to_json_vec, StdResult, Timestamp};
+use cosmwasm_std::{from_json, to_json_vec, Binary, StdResult, Timestamp};
 
 use serde::{de::DeserializeOwned, Serialize};
 
 use snb_base::{
-    constants::ENC_KEY_LEN,
+    constants::{ENC_KEY_LEN, NONCE_LEN},
     converters::{timestamp_to_nonce, utf8_vec_to_str},
     private_communication::types::EncryptedResponse,
 };
@@ -18,20 +18,32 @@ pub fn deserialize<T: DeserializeOwned>(data: &str) -> StdResult<T> {
     from_json::<T>(data.as_bytes())
 }
 
-pub fn decrypt_deserialize<T, F>(enc_key: &F, timestamp: &Timestamp, value: &str) -> StdResult<T>
+/// if nonce isn't specified it will be derived from the timestamp to decode packets in legacy format
+pub fn decrypt_deserialize<T, F>(
+    enc_key: &F,
+    timestamp: &Timestamp,
+    nonce: Option<&Binary>,
+    value: &str,
+) -> StdResult<T>
 where
     T: DeserializeOwned,
     F: Into<[u8; ENC_KEY_LEN]> + Clone,
 {
     let encryption_key = &enc_key.to_owned().into();
-    let nonce = &timestamp_to_nonce(timestamp);
-    let decrypted_data = &decrypt(value, encryption_key, nonce)?;
+    let nonce = match nonce {
+        Some(x) => x.to_vec(),
+        None => timestamp_to_nonce(timestamp).into_bytes(),
+    };
+    let decrypted_data = &decrypt(value, encryption_key, &nonce)?;
 
     deserialize(decrypted_data)
 }
 
+/// nonce must be unique for each message encrypted with the same key
 pub fn serialize_encrypt<T, F>(
     enc_key: &F,
+    key_id: u64,
+    nonce: &[u8; NONCE_LEN],
     timestamp: &Timestamp,
     value: &T,
 ) -> StdResult<EncryptedResponse>
@@ -40,12 +52,13 @@ where
     F: Into<[u8; ENC_KEY_LEN]> + Clone,
 {
     let encryption_key = &enc_key.to_owned().into();
-    let nonce = &timestamp_to_nonce(timestamp);
     let serialized_value = &serialize(value)?;
     let encrypted_value = encrypt(serialized_value, encryption_key, nonce)?;
 
     Ok(EncryptedResponse {
         value: encrypted_value,
         timestamp: timestamp.to_owned(),
+        key_id,
+        nonce: Some(Binary::from(nonce.as_slice())),
     })
 }
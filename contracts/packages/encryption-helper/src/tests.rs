@@ -1,4 +1,11 @@
-use crate::base::{decrypt, encrypt};
+use cosmwasm_std::Timestamp;
+
+use snb_base::converters::{get_nonce, timestamp_to_nonce};
+
+use crate::{
+    base::{decrypt, encrypt},
+    serde::{decrypt_deserialize, serialize, serialize_encrypt},
+};
 
 use speculoos::assert_that;
 
@@ -6,7 +13,7 @@ use speculoos::assert_that;
 fn default_ecryption() {
     const MESSAGE: &str = "The secret message #1. Don't share it!⚠️";
     const ENC_KEY: &[u8; 32] = &[1; 32];
-    const NONCE: &str = "unique nonce";
+    const NONCE: &[u8] = b"unique nonce";
 
     let encrypted = encrypt(MESSAGE, ENC_KEY, NONCE).unwrap();
     let decrypted = decrypt(&encrypted, ENC_KEY, NONCE).unwrap();
@@ -14,3 +21,58 @@ fn default_ecryption() {
     assert_that(&encrypted).is_not_equal_to(&decrypted);
     assert_that(&MESSAGE).is_equal_to(&*decrypted);
 }
+
+#[test]
+fn wrong_nonce_length() {
+    const MESSAGE: &str = "The secret message #1. Don't share it!⚠️";
+    const ENC_KEY: &[u8; 32] = &[1; 32];
+    const NONCE: &[u8] = b"short nonce";
+
+    let res = encrypt(MESSAGE, ENC_KEY, NONCE);
+
+    assert_that(&res.is_err()).is_equal_to(true);
+}
+
+#[test]
+fn unique_nonce_ecryption() {
+    const MESSAGE: &str = "The secret message #1. Don't share it!⚠️";
+    const ENC_KEY: [u8; 32] = [1; 32];
+    const CONTRACT: &str = "neutron1transceiver";
+    let timestamp = Timestamp::from_nanos(1_571_797_419_879_305_533);
+
+    let encrypted_1 =
+        serialize_encrypt(&ENC_KEY, 1, &get_nonce(CONTRACT, 1), &timestamp, MESSAGE).unwrap();
+    let encrypted_2 =
+        serialize_encrypt(&ENC_KEY, 1, &get_nonce(CONTRACT, 2), &timestamp, MESSAGE).unwrap();
+    let decrypted: String = decrypt_deserialize(
+        &ENC_KEY,
+        &timestamp,
+        encrypted_1.nonce.as_ref(),
+        &encrypted_1.value,
+    )
+    .unwrap();
+
+    assert_that(&encrypted_1.value).is_not_equal_to(&encrypted_2.value);
+    assert_that(&MESSAGE).is_equal_to(&*decrypted);
+}
+
+#[test]
+fn contract_nonce_separation() {
+    let nonce_hub = get_nonce("neutron1transceiver", 1);
+    let nonce_outpost = get_nonce("stars1transceiver", 1);
+
+    assert_that(&nonce_hub).is_not_equal_to(nonce_outpost);
+}
+
+#[test]
+fn legacy_nonce_decryption() {
+    const MESSAGE: &str = "The secret message #1. Don't share it!⚠️";
+    const ENC_KEY: [u8; 32] = [1; 32];
+    let timestamp = Timestamp::from_nanos(1_571_797_419_879_305_533);
+
+    let nonce = timestamp_to_nonce(&timestamp);
+    let encrypted = encrypt(&serialize(MESSAGE).unwrap(), &ENC_KEY, nonce.as_bytes()).unwrap();
+    let decrypted: String = decrypt_deserialize(&ENC_KEY, &timestamp, None, &encrypted).unwrap();
+
+    assert_that(&MESSAGE).is_equal_to(&*decrypted);
+}
@@ -3,4 +3,8 @@ pub const DECIMALS_DEFAULT: u8 = 6;
 pub const MINS_PER_DAY: u128 = 24 * 60;
 pub const NANOS_PER_MIN: u128 = 60 * 1_000_000_000;
 
+pub const MIN_IBC_TIMEOUT: u64 = 60;
+pub const MAX_IBC_TIMEOUT: u64 = 24 * 3600;
+
 pub const ENC_KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
@@ -2,9 +2,10 @@ use std::str::FromStr;
 
 use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Timestamp, Uint128, Uint256};
 
-use bech32::{decode, encode, Variant};
+use bech32::{decode, encode, ToBase32, Variant};
+use sha2::{Digest, Sha256};
 
-use crate::constants::ENC_KEY_LEN;
+use crate::constants::{ENC_KEY_LEN, NONCE_LEN};
 
 pub fn str_to_dec(s: &str) -> Decimal {
     Decimal::from_str(s).unwrap()
@@ -71,13 +72,63 @@ pub fn utf8_vec_to_str(v: &[u8]) -> StdResult<String> {
         .map_err(|e| StdError::generic_err(e.to_string()))
 }
 
+/// legacy nonce, it's the same for all messages sent within ~16 minutes
 pub fn timestamp_to_nonce(timestamp: &Timestamp) -> String {
     // Nonce length must be 12
     timestamp.nanos().to_string()[..12].to_string()
 }
 
+/// Combines per-contract prefix (4 bytes) and unique counter (8 bytes) into nonce. \
+/// The hub and outposts share the key, so the prefix derived from the contract address
+/// separates their nonces
+pub fn get_nonce(contract_address: &str, counter: u64) -> [u8; NONCE_LEN] {
+    let mut nonce = [0; NONCE_LEN];
+    nonce[..4].copy_from_slice(&Sha256::digest(contract_address.as_bytes())[..4]);
+    nonce[4..].copy_from_slice(&counter.to_be_bytes());
+
+    nonce
+}
+
 pub fn get_addr_by_prefix(address: impl ToString, prefix: &str) -> StdResult<String> {
     let (_hrp, data, _) =
         decode(&address.to_string()).map_err(|e| StdError::generic_err(e.to_string()))?;
     encode(prefix, data, Variant::Bech32).map_err(|e| StdError::generic_err(e.to_string()))
 }
+
+/// Returns address used by ibc-hooks to execute wasm msg on behalf of original sender \
+/// https://github.com/osmosis-labs/osmosis/blob/main/x/ibc-hooks/keeper/keeper.go
+pub fn get_ibc_hooks_sender(
+    channel: &str,
+    original_sender: &str,
+    prefix: &str,
+) -> StdResult<String> {
+    const SENDER_PREFIX: &str = "ibc-wasm-hook-intermediary";
+
+    derive_address(SENDER_PREFIX, channel, original_sender, prefix, 32)
+}
+
+/// Returns address used by packet-forward-middleware to forward transfer of original sender \
+/// https://github.com/cosmos/ibc-apps/blob/main/middleware/packet-forward-middleware/packetforward/types/keys.go
+pub fn get_pfm_sender(channel: &str, original_sender: &str, prefix: &str) -> StdResult<String> {
+    // the typo is in the module name
+    const MODULE_NAME: &str = "packetfowardmiddleware";
+
+    derive_address(MODULE_NAME, channel, original_sender, prefix, 20)
+}
+
+fn derive_address(
+    module: &str,
+    channel: &str,
+    original_sender: &str,
+    prefix: &str,
+    len: usize,
+) -> StdResult<String> {
+    let type_hash = Sha256::digest(module.as_bytes());
+    let sender_hash = Sha256::new()
+        .chain_update(type_hash)
+        .chain_update(format!("{}/{}", channel, original_sender).as_bytes())
+        .finalize();
+
+    encode(prefix, (&sender_hash[..len]).to_base32(), Variant::Bech32)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
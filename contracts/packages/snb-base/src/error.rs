@@ -214,4 +214,61 @@ pub enum ContractError {
 
     #[error("User is not found!")]
     UserIsNotFound,
+
+    #[error("Packet is already processed!")]
+    PacketDuplication,
+
+    #[error("Encryption key is not found!")]
+    EncKeyIsNotFound,
+
+    #[error("Encryption key is expired!")]
+    EncKeyIsExpired,
+
+    #[error("Transfer is not found!")]
+    TransferIsNotFound,
+
+    #[error("Wrong IBC channel parameters!")]
+    WrongIbcChannel,
+
+    #[error("Wrong class id!")]
+    WrongClassId,
+
+    #[error("Multi-hop route is available only for ICS-20 transport!")]
+    RouteIsNotSupported,
+
+    #[error("Channel id must have format channel-N!")]
+    WrongChannelId,
+
+    #[error("The collection is temporary paused!")]
+    CollectionIsPaused,
+
+    #[error("Collection daily quota is exceeded!")]
+    ExceededDailyQuota,
+
+    #[error("Packet exceeds {max:?} bytes of ibc transfer memo, send fewer tokens!")]
+    ExceededMemoSize { max: usize },
+
+    #[error("Control packets are disabled!")]
+    HubControlIsDisabled,
+
+    #[error("Control packets are available only for ICS-20 transport!")]
+    ControlIsNotSupported,
+
+    #[error("Wrong IBC timeout bounds!")]
+    WrongTimeoutBounds,
+
+    #[error("Metadata list doesn't match the token list!")]
+    WrongMetadataList,
+
+    #[error("Code id of the collection flavor isn't specified!")]
+    CodeIdIsNotFound,
+
+    #[error("Royalty share must not exceed 100%!")]
+    WrongRoyaltyShare,
+
+    #[error("Minter is not found!")]
+    MinterIsNotFound,
+
+    #[error("Nft minter isn't the collection minter!")]
+    WrongCollectionMinter,
 }
@@ -20,18 +20,65 @@ pub enum ExecuteMsg {
     UpdateConfig {
         admin: Option<String>,
         wrapper: Option<String>,
+        cw721_metadata_onchain_code_id: Option<u64>,
+        sg721_code_id: Option<u64>,
+        cw2981_code_id: Option<u64>,
     },
 
+    /// can be called by admin or transceiver hub,
+    /// label is "Simple NFT Bridge collection: {name}" if it isn't specified,
+    /// cw721-base is instantiated if flavor isn't specified, sg721 requires image,
+    /// royalty_info is applied to minted tokens without their own royalty,
+    /// cw2981 collection stores integer percentage so royalty share is rounded half up
+    /// to the whole percent on mint
     CreateCollection {
         name: String,
+        symbol: Option<String>,
+        description: Option<String>,
+        image: Option<String>,
+        external_link: Option<String>,
+        label: Option<String>,
+        flavor: Option<super::types::CollectionFlavor>,
+        royalty_info: Option<super::types::RoyaltyInfo>,
+        /// is granted to mint and burn the collection tokens, if it isn't specified
+        /// the transceiver hub is granted only if it's the caller
+        minter: Option<String>,
     },
 
+    /// admin only, imports cw721 contract minted by nft-minter,
+    /// flavor is defined by code id, royalty is read from sg721 collection info,
+    /// cw2981 royalties stay per token
+    RegisterCollection {
+        address: String,
+        /// label of cw721 contract instance, collection name is used by default
+        label: Option<String>,
+        /// is granted to mint and burn the collection tokens, if it isn't specified
+        /// the transceiver hub is granted only if it's the caller
+        minter: Option<String>,
+    },
+
+    /// admin only, transceiver hub is granted on collection creation
+    GrantMinter {
+        collection: String,
+        minter: String,
+    },
+
+    /// admin only
+    RevokeMinter {
+        collection: String,
+        minter: String,
+    },
+
+    /// can be called by collection minters only,
+    /// metadata_list is empty or has the same length as token_list
     Mint {
         collection: String,
         token_list: Vec<String>,
         recipient: String,
+        metadata_list: Option<Vec<super::types::TokenMetadata>>,
     },
 
+    /// can be called by collection minters only
     Burn {
         collection: String,
         token_list: Vec<String>,
@@ -44,12 +91,15 @@ pub enum QueryMsg {
     #[returns(super::types::Config)]
     Config {},
 
-    #[returns(String)]
+    #[returns(super::types::CollectionInfo)]
     Collection { address: String },
 
-    #[returns(Vec<(Addr, String)>)]
+    #[returns(Vec<(Addr, super::types::CollectionInfo)>)]
     CollectionList {
         amount: u32,
         start_after: Option<String>,
     },
+
+    #[returns(Vec<Addr>)]
+    Minters { collection: String },
 }
@@ -1,7 +1,7 @@
 use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
 
-use super::types::{Config, TransferAdminState};
+use super::types::{CollectionInfo, Config, TransferAdminState};
 
 pub const CONTRACT_NAME: &str = "snb-nft-minter";
 
@@ -12,5 +12,9 @@ pub const CONFIG: Item<Config> = Item::new("config");
 
 /// Stores the state of changing admin process
 pub const TRANSFER_ADMIN_STATE: Item<TransferAdminState> = Item::new("transfer_admin_state");
-/// colletion name by address
-pub const COLLECTIONS: Map<&Addr, String> = Map::new("collections");
+/// colletion info by address
+pub const COLLECTIONS: Map<&Addr, CollectionInfo> = Map::new("collections");
+/// addresses allowed to mint and burn tokens of the collection: (collection, minter) -> true
+pub const MINTERS: Map<(&Addr, &Addr), bool> = Map::new("minters");
+/// minter of the collection being instantiated, it's granted on reply
+pub const PENDING_MINTER: Item<Addr> = Item::new("pending_minter");
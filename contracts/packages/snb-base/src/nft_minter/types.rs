@@ -1,5 +1,7 @@
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_schema::{cw_serde, schemars::JsonSchema};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+
+use serde::{Deserialize, Serialize};
 
 #[cw_serde]
 pub struct Config {
@@ -7,6 +9,56 @@ pub struct Config {
     pub transceiver_hub: Addr,
     pub wrapper: Option<Addr>,
     pub cw721_code_id: u64,
+    pub cw721_metadata_onchain_code_id: Option<u64>,
+    pub sg721_code_id: Option<u64>,
+    pub cw2981_code_id: Option<u64>,
+}
+
+impl Config {
+    pub fn get_code_id(&self, flavor: &CollectionFlavor) -> Option<u64> {
+        match flavor {
+            CollectionFlavor::Cw721Base => Some(self.cw721_code_id),
+            CollectionFlavor::Cw721MetadataOnchain => self.cw721_metadata_onchain_code_id,
+            CollectionFlavor::Sg721 => self.sg721_code_id,
+            CollectionFlavor::Cw2981 => self.cw2981_code_id,
+        }
+    }
+}
+
+/// cw721 contract type, it defines the extension used to mint nfts
+#[cw_serde]
+#[derive(Default)]
+pub enum CollectionFlavor {
+    /// extension isn't stored
+    #[default]
+    Cw721Base,
+    /// extension is Option<Metadata>
+    Cw721MetadataOnchain,
+    /// extension is Metadata, collection info is passed on instantiation
+    Sg721,
+    /// extension is Option<Cw2981Metadata>, royalties are stored per token
+    Cw2981,
+}
+
+#[cw_serde]
+pub struct CollectionInfo {
+    pub name: String,
+    pub symbol: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub external_link: Option<String>,
+    /// label of cw721 contract instance
+    pub label: String,
+    #[serde(default)]
+    pub flavor: CollectionFlavor,
+    /// default royalty of the collection tokens
+    pub royalty_info: Option<RoyaltyInfo>,
+}
+
+/// cw-ownable ownership of cw721 contract, pending ownership is ignored
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Cw721OwnershipResponse {
+    pub owner: Option<String>,
 }
 
 #[cw_serde]
@@ -14,3 +66,118 @@ pub struct TransferAdminState {
     pub new_admin: Addr,
     pub deadline: u64,
 }
+
+/// token_uri and on-chain metadata of the bridged nft
+#[cw_serde]
+#[derive(Default)]
+pub struct TokenMetadata {
+    pub token_uri: Option<String>,
+    pub extension: Option<Metadata>,
+    #[serde(default)]
+    pub royalty_info: Option<RoyaltyInfo>,
+}
+
+/// royalty share of the sale price and its recipient
+#[cw_serde]
+pub struct RoyaltyInfo {
+    pub payment_address: String,
+    pub share: Decimal,
+}
+
+// https://github.com/CosmWasm/cw-nfts/blob/main/contracts/cw721-metadata-onchain/src/lib.rs
+/// unknown fields are ignored as collections can extend the standard metadata
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[schemars(crate = "::cosmwasm_schema::schemars")]
+pub struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<Vec<Trait>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub youtube_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[schemars(crate = "::cosmwasm_schema::schemars")]
+pub struct Trait {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+// https://github.com/public-awesome/launchpad/blob/main/packages/sg721/src/lib.rs
+#[cw_serde]
+pub struct Sg721InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub minter: String,
+    pub collection_info: Sg721CollectionInfo,
+}
+
+#[cw_serde]
+pub struct Sg721CollectionInfo {
+    pub creator: String,
+    pub description: String,
+    pub image: String,
+    pub external_link: Option<String>,
+    pub explicit_content: Option<bool>,
+    pub start_trading_time: Option<Timestamp>,
+    pub royalty_info: Option<RoyaltyInfo>,
+}
+
+#[cw_serde]
+pub enum Sg721QueryMsg {
+    CollectionInfo {},
+}
+
+/// only royalty info is used, other fields are ignored
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Sg721CollectionInfoResponse {
+    pub royalty_info: Option<RoyaltyInfo>,
+}
+
+// https://github.com/CosmWasm/cw-nfts/blob/main/contracts/cw2981-royalties/src/lib.rs
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Cw2981Metadata {
+    #[serde(flatten)]
+    pub metadata: Metadata,
+    /// integer percentage of the sale price, 0..=100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_percentage: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_payment_address: Option<String>,
+}
+
+/// it's wrapped into cw721-base extension query
+#[cw_serde]
+pub enum Cw2981QueryMsg {
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+    CheckRoyalties {},
+}
+
+#[cw_serde]
+pub struct Cw2981RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct Cw2981CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
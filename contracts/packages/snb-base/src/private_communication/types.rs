@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Env, StdResult, Timestamp};
+use cosmwasm_std::{Binary, Env, StdResult, Timestamp};
 
 use crate::{
     constants::ENC_KEY_LEN,
@@ -10,6 +10,11 @@ use crate::{
 pub struct EncryptedResponse {
     pub value: String,
     pub timestamp: Timestamp,
+    /// id of the key used for encryption
+    pub key_id: u64,
+    /// unique nonce of the encrypted value, packets in legacy format don't have it
+    /// so their nonce is derived from the timestamp
+    pub nonce: Option<Binary>,
 }
 
 #[cw_serde]
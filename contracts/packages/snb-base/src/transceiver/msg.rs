@@ -1,11 +1,19 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Timestamp, Uint128};
+use cosmwasm_std::{Binary, Coin, Timestamp, Uint128};
 
-use super::types::{Height, RequestPacket, TransceiverType};
+use crate::nft_minter::types::{CollectionFlavor, TokenMetadata};
+
+use super::types::{
+    ControlAction, Height, Hop, IbcLifecycleComplete, RequestPacket, TimeoutBounds,
+    TransceiverType, Transport,
+};
 
 #[cw_serde]
 pub struct MigrateMsg {
     pub version: String,
+    /// is required if the contract doesn't store encryption key yet,
+    /// it must be the hardcoded key to decrypt packets sent before migration
+    pub enc_key: Option<String>,
 }
 
 #[cw_serde]
@@ -15,6 +23,8 @@ pub struct InstantiateMsg {
     pub transceiver_type: TransceiverType,
     pub token_limit: Option<u8>,
     pub min_ntrn_ibc_fee: Option<Uint128>,
+    /// must be the same for the hub and all outposts
+    pub enc_key: String,
 }
 
 #[cw_serde]
@@ -31,6 +41,29 @@ pub enum ExecuteMsg {
         hub_address: Option<String>,
         token_limit: Option<u8>,
         min_ntrn_ibc_fee: Option<Uint128>,
+        /// outpost only
+        hub_control: Option<bool>,
+        timeout_bounds: Option<TimeoutBounds>,
+    },
+
+    /// hub only, replaces the outpost with the same prefix
+    RegisterOutpost {
+        address: String,
+        /// must be specified if the outpost isn't on the hub chain
+        from_hub: Option<String>,
+        to_hub: Option<String>,
+        /// ICS-20 is used by default
+        transport: Option<Transport>,
+        /// intermediate chains ordered from the hub to the outpost, ICS-20 only
+        route: Option<Vec<Hop>>,
+        chain_id: Option<String>,
+        /// default relative ibc timeout in seconds
+        timeout: Option<u64>,
+    },
+
+    /// hub only
+    RemoveOutpost {
+        prefix: String,
     },
 
     AddCollection {
@@ -42,10 +75,57 @@ pub enum ExecuteMsg {
         hub_collection: String,
     },
 
+    /// hub only, creates hub collection through nft minter and adds the collection pair.
+    /// name and symbol are queried from the home collection if it's deployed on the hub network,
+    /// otherwise both of them must be specified
+    CreateCollection {
+        home_collection: String,
+        name: Option<String>,
+        symbol: Option<String>,
+        /// cw721 contract type of the hub collection, cw721-base by default
+        flavor: Option<CollectionFlavor>,
+    },
+
+    /// zero token_limit, fee or daily_quota and empty royalty_recipient reset the setting
+    UpdateCollection {
+        hub_collection: String,
+        token_limit: Option<u8>,
+        is_paused: Option<bool>,
+        fee: Option<Coin>,
+        daily_quota: Option<u32>,
+        /// hub only
+        royalty_recipient: Option<String>,
+    },
+
+    /// outpost only, the hub uses channels of registered outposts
     SetChannel {
         prefix: String,
         from_hub: String,
         to_hub: String,
+        /// ICS-20 is used by default
+        transport: Option<Transport>,
+        /// intermediate chains ordered from the hub to the outpost, ICS-20 only
+        route: Option<Vec<Hop>>,
+        /// counterparty chain id
+        chain_id: Option<String>,
+        /// default relative ibc timeout in seconds
+        timeout: Option<u64>,
+    },
+
+    /// outpost only
+    RemoveChannel {
+        prefix: String,
+    },
+
+    /// outpost only, the hub always uses untrn carrier. empty list allows any denom
+    SetCarrierDenoms {
+        prefix: String,
+        denoms: Vec<String>,
+    },
+
+    /// replaces the current encryption key, it must be done on the hub and all outposts
+    RotateKey {
+        enc_key: String,
     },
 
     Send {
@@ -53,11 +133,44 @@ pub enum ExecuteMsg {
         token_list: Vec<String>,
         /// if specified will send to the contract on the same chain
         target: Option<String>,
+        /// relative ibc timeout in seconds, it's clamped to Config.timeout_bounds.
+        /// the channel timeout is used if it isn't specified
+        timeout: Option<u64>,
     },
 
     Accept {
         msg: String,
         timestamp: Timestamp,
+        /// is missing in packets sent before key rotation was introduced
+        #[serde(default)]
+        key_id: u64,
+        nonce: Option<Binary>,
+    },
+
+    /// hub only, sends control packet to the outpost, 1 untrn carrier plus ack and timeout fees
+    /// are required
+    SendControl {
+        prefix: String,
+        action: ControlAction,
+    },
+
+    /// outpost only, applies encrypted control packet sent by the hub
+    Control {
+        msg: String,
+        timestamp: Timestamp,
+        key_id: u64,
+        nonce: Option<Binary>,
+    },
+
+    /// can be executed only by the contract itself, mints or unlocks nfts received over ibc
+    /// channel in a single submessage so the packet is either fully applied or fully reverted
+    ReceiveNfts {
+        hub_collection: String,
+        token_list: Vec<String>,
+        metadata_list: Vec<TokenMetadata>,
+        recipient: String,
+        /// (sender, id) of native packet, it's saved as processed with the received nfts
+        processed_packet: Option<(String, u64)>,
     },
 }
 
@@ -71,8 +184,11 @@ pub enum QueryMsg {
     PauseState {},
 
     /// works well only for TransceiverType::Hub
-    #[returns(Vec<String>)]
-    Outposts {},
+    #[returns(Vec<super::types::Outpost>)]
+    Outposts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     #[returns(super::types::Collection)]
     Collection {
@@ -81,10 +197,60 @@ pub enum QueryMsg {
     },
 
     #[returns(Vec<super::types::Collection>)]
-    CollectionList {},
+    CollectionList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
+    /// outpost only
     #[returns(Vec<super::types::Channel>)]
-    ChannelList {},
+    ChannelList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// returns true if the packet was already accepted
+    #[returns(bool)]
+    PacketState { sender: String, packet_id: u64 },
+
+    #[returns(super::types::Transfer)]
+    Transfer { id: u64 },
+
+    #[returns(Vec<super::types::Transfer>)]
+    TransfersBySender {
+        sender: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<super::types::Transfer>)]
+    PendingTransfers {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// carrier coin required to send the collection tokens over ibc
+    #[returns(super::types::CarrierRequirement)]
+    CarrierRequirement { hub_collection: String },
+
+    /// hub only
+    #[returns(super::types::FeeBalance)]
+    FeeBalance {},
+
+    /// hub only, neutron fee refunder min fees or the configured minimum if they're unavailable
+    #[returns(super::types::IbcFee)]
+    FeeEstimate {},
+
+    /// hub only
+    #[returns(super::types::Control)]
+    Control { id: u64 },
+
+    /// hub only
+    #[returns(Vec<super::types::Control>)]
+    ControlList {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 // https://github.com/neutron-org/neutron-sdk/blob/main/packages/neutron-sdk/src/sudo/msg.rs
@@ -116,4 +282,6 @@ pub enum SudoMsg {
     KVQueryResult {
         query_id: u64,
     },
+    /// ibc-hooks callback for ibc transfers sent from outposts
+    IbcLifecycleComplete(IbcLifecycleComplete),
 }
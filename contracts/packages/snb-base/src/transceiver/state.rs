@@ -1,16 +1,32 @@
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 
-use super::types::{Channel, Collection, Config, TransferAdminState};
+use super::types::{
+    Channel, Collection, Config, Control, DailyUsage, EncKey, Outpost, Transfer, TransferAdminState,
+};
 
 pub const CONTRACT_NAME: &str = "snb-transceiver";
 
+pub const SAVE_IBC_SEQUENCE_REPLY: u64 = 0;
+pub const IBC_RECEIVE_REPLY: u64 = 1;
+pub const CREATE_COLLECTION_REPLY: u64 = 2;
+pub const SAVE_CONTROL_SEQUENCE_REPLY: u64 = 3;
+
+pub const PAGINATION_DEFAULT_LIMIT: u32 = 10;
+pub const PAGINATION_MAX_LIMIT: u32 = 100;
+
 pub const TRANSFER_ADMIN_TIMEOUT: u64 = 7 * 24 * 3600;
 pub const TOKEN_LIMIT: u8 = 10;
+pub const SECONDS_PER_DAY: u64 = 24 * 3600;
 // https://rest-kralum.neutron-1.neutron.org/neutron-org/neutron/feerefunder/params
 pub const MIN_NTRN_IBC_FEE: u128 = 100_000;
+/// cw2981 royalty amount is queried for this price to get the royalty share
+pub const ROYALTY_SALE_PRICE: u128 = 1_000_000;
 
-// TODO: replace before storing the contract
-pub const ENC_KEY: &str = "qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq";
+/// previous encryption key can be used for decryption during this period after rotation
+pub const ENC_KEY_GRACE_PERIOD: u64 = 24 * 3600;
+/// key id of packets sent before key rotation was introduced
+pub const LEGACY_ENC_KEY_ID: u64 = 0;
 
 pub const DENOM_NTRN: &str = "untrn";
 
@@ -21,13 +37,97 @@ pub const CHANNEL_STARGAZE_NEUTRON: &str = "channel-191";
 pub const CHANNEL_NEUTRON_STARGAZE: &str = "channel-18";
 
 pub const PORT: &str = "transfer";
+pub const ICS721_VERSION: &str = "ics721-1";
+pub const NATIVE_VERSION: &str = "snb-1";
 pub const IBC_TIMEOUT: u64 = 10 * 60;
+/// receiver on intermediate chains is replaced by packet-forward-middleware
+pub const PFM_RECEIVER: &str = "pfm";
+pub const PFM_RETRIES: u8 = 2;
+/// ibc-go rejects ICS-20 transfers with longer memo
+pub const MAX_IBC_MEMO_LEN: usize = 32_768;
 
 pub const IS_PAUSED: Item<bool> = Item::new("is_paused");
 pub const CONFIG: Item<Config> = Item::new("config");
 
 /// Stores the state of changing admin process
 pub const TRANSFER_ADMIN_STATE: Item<TransferAdminState> = Item::new("transfer_admin_state");
-pub const OUTPOSTS: Item<Vec<String>> = Item::new("outposts");
-pub const COLLECTIONS: Item<Vec<Collection>> = Item::new("collections");
-pub const CHANNELS: Item<Vec<Channel>> = Item::new("channels");
+/// Outposts registered by admin, stored by prefix
+pub const OUTPOSTS: Map<&str, Outpost> = Map::new("outposts");
+/// Home collection of the hub collection being created, it's added on reply
+pub const PENDING_HOME_COLLECTION: Item<String> = Item::new("pending_home_collection");
+/// Sent tokens by hub collection, it's tracked only for collections with daily quota
+pub const DAILY_USAGE: Map<&str, DailyUsage> = Map::new("daily_usage");
+/// ibc channels by counterparty chain prefix
+pub const CHANNELS: Map<&str, Channel> = Map::new("channels");
+/// versions negotiated on opening the contract's own ibc channels by channel id
+pub const CHANNEL_VERSIONS: Map<&str, String> = Map::new("channel_versions");
+
+/// Key used to encrypt sent packets and decrypt received ones
+pub const ENC_KEY: Item<EncKey> = Item::new("enc_key");
+/// Key replaced on the last rotation, it's accepted until expiration
+pub const PREVIOUS_ENC_KEY: Item<EncKey> = Item::new("previous_enc_key");
+/// Number of encrypted messages, it's used to generate unique nonces
+pub const NONCE_COUNTER: Item<u64> = Item::new("nonce_counter");
+
+/// Id of the last packet sent by the contract
+pub const PACKET_ID: Item<u64> = Item::new("packet_id");
+/// Accepted packets by (sender, packet id)
+pub const PROCESSED_PACKETS: Map<(&str, u64), bool> = Map::new("processed_packets");
+
+/// Transfer ids by (source channel, sequence) of ibc transfer
+pub const TRANSFER_IDS: Map<(&str, u64), u64> = Map::new("transfer_ids");
+/// untrn escrowed by neutron fee refunder for pending transfers
+pub const IBC_FEE_ESCROW: Item<Uint128> = Item::new("ibc_fee_escrow");
+
+/// Control packets sent by the hub by id
+pub const CONTROLS: Map<u64, Control> = Map::new("controls");
+/// Control packet ids by (source channel, sequence) of ibc transfer
+pub const CONTROL_IDS: Map<(&str, u64), u64> = Map::new("control_ids");
+
+pub struct TransferIndexes<'a> {
+    pub sender: MultiIndex<'a, Addr, Transfer, u64>,
+    pub status: MultiIndex<'a, String, Transfer, u64>,
+}
+
+impl<'a> IndexList<Transfer> for TransferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Transfer>> + '_> {
+        let v: Vec<&dyn Index<Transfer>> = vec![&self.sender, &self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Outbound transfers by id
+pub fn transfers<'a>() -> IndexedMap<'a, u64, Transfer, TransferIndexes<'a>> {
+    let indexes = TransferIndexes {
+        sender: MultiIndex::new(|_, x| x.sender.to_owned(), "transfers", "transfers__sender"),
+        status: MultiIndex::new(
+            |_, x| x.status.to_string(),
+            "transfers",
+            "transfers__status",
+        ),
+    };
+
+    IndexedMap::new("transfers", indexes)
+}
+
+pub struct CollectionIndexes<'a> {
+    pub hub: UniqueIndex<'a, String, Collection, String>,
+    pub home: UniqueIndex<'a, String, Collection, String>,
+}
+
+impl<'a> IndexList<Collection> for CollectionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Collection>> + '_> {
+        let v: Vec<&dyn Index<Collection>> = vec![&self.hub, &self.home];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Collections by hub collection address
+pub fn collections<'a>() -> IndexedMap<'a, &'a str, Collection, CollectionIndexes<'a>> {
+    let indexes = CollectionIndexes {
+        hub: UniqueIndex::new(|x| x.hub_collection.to_owned(), "collections__hub"),
+        home: UniqueIndex::new(|x| x.home_collection.to_owned(), "collections__home"),
+    };
+
+    IndexedMap::new("collections", indexes)
+}
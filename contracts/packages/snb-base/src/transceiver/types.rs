@@ -1,5 +1,12 @@
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_schema::{cw_serde, schemars::JsonSchema};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp, Uint128};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{MAX_IBC_TIMEOUT, MIN_IBC_TIMEOUT},
+    nft_minter::types::{RoyaltyInfo, TokenMetadata},
+};
 
 #[cw_serde]
 pub enum TransceiverType {
@@ -11,6 +18,40 @@ pub enum TransceiverType {
 pub struct Collection {
     pub home_collection: String,
     pub hub_collection: String,
+    /// overrides Config.token_limit if specified
+    pub token_limit: Option<u8>,
+    /// sending and receiving the collection tokens is disabled
+    #[serde(default)]
+    pub is_paused: bool,
+    /// protocol fee paid on send in addition to ibc fees
+    pub fee: Option<Coin>,
+    /// max amount of tokens sent per day
+    pub daily_quota: Option<u32>,
+    /// hub account receiving royalties of the bridged tokens, if it isn't specified
+    /// home chain payment addresses are converted to the hub prefix
+    #[serde(default)]
+    pub royalty_recipient: Option<String>,
+}
+
+impl Collection {
+    pub fn new(hub_collection: &str, home_collection: &str) -> Self {
+        Self {
+            home_collection: home_collection.to_string(),
+            hub_collection: hub_collection.to_string(),
+            token_limit: None,
+            is_paused: false,
+            fee: None,
+            daily_quota: None,
+            royalty_recipient: None,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct DailyUsage {
+    /// days since unix epoch
+    pub day: u64,
+    pub token_amount: u32,
 }
 
 #[cw_serde]
@@ -21,6 +62,41 @@ pub struct Config {
     pub transceiver_type: TransceiverType,
     pub token_limit: u8,
     pub min_ntrn_ibc_fee: Uint128,
+    /// outpost applies control packets sent by the hub
+    #[serde(default)]
+    pub hub_control: bool,
+    #[serde(default)]
+    pub timeout_bounds: TimeoutBounds,
+}
+
+/// bounds of relative ibc timeout in seconds
+#[cw_serde]
+pub struct TimeoutBounds {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Default for TimeoutBounds {
+    fn default() -> Self {
+        Self {
+            min: MIN_IBC_TIMEOUT,
+            max: MAX_IBC_TIMEOUT,
+        }
+    }
+}
+
+impl TimeoutBounds {
+    pub fn clamp(&self, timeout: u64) -> u64 {
+        timeout.clamp(self.min, self.max)
+    }
+}
+
+#[cw_serde]
+pub struct Outpost {
+    pub prefix: String,
+    pub address: String,
+    /// isn't specified for the outpost on the hub chain
+    pub channel: Option<Channel>,
 }
 
 #[cw_serde]
@@ -31,33 +107,237 @@ pub struct TransferAdminState {
 
 #[cw_serde]
 pub struct Packet {
+    /// unique for the sender, it's used for replay protection
+    pub id: u64,
     pub sender: String,
     pub recipient: String,
     pub hub_collection: String,
     pub home_collection: String,
     pub token_list: Vec<String>,
+    /// token_uri and extension of each token, it's empty if the metadata isn't provided
+    #[serde(default)]
+    pub metadata_list: Vec<TokenMetadata>,
+    /// royalty of the home collection, it's applied to tokens without their own royalty
+    #[serde(default)]
+    pub royalty_info: Option<RoyaltyInfo>,
+}
+
+#[cw_serde]
+pub struct EncKey {
+    pub id: u64,
+    pub value: String,
+    /// is specified only for the previous key
+    pub expiration: Option<Timestamp>,
 }
 
 #[cw_serde]
 pub enum IbcMemo<M> {
-    Forward {
-        channel: String,
-        port: String,
-        receiver: String,
-        retries: u8,
-        timeout: u64,
+    Forward(ForwardMemo<M>),
+    Wasm { contract: String, msg: M },
+}
+
+/// packet-forward-middleware memo, next memo is executed on the next chain
+/// https://github.com/cosmos/ibc-apps/tree/main/middleware/packet-forward-middleware
+#[cw_serde]
+pub struct ForwardMemo<M> {
+    pub receiver: String,
+    pub port: String,
+    pub channel: String,
+    pub retries: u8,
+    /// relative timeout in nanoseconds
+    pub timeout: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<IbcMemo<M>>>,
+}
+
+/// ibc-hooks memo requesting ack/timeout callback for the sender contract
+/// https://github.com/osmosis-labs/osmosis/tree/main/x/ibc-hooks#interface-for-receiving-the-acks-and-timeouts
+#[cw_serde]
+pub struct IbcHooksMemo<M> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm: Option<WasmMemo<M>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward: Option<ForwardMemo<M>>,
+    pub ibc_callback: String,
+}
+
+#[cw_serde]
+pub struct WasmMemo<M> {
+    pub contract: String,
+    pub msg: M,
+}
+
+#[cw_serde]
+pub enum TransferStatus {
+    Pending,
+    Delivered,
+    Refunded,
+}
+
+impl std::fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let status = match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Refunded => "refunded",
+        };
+
+        write!(f, "{}", status)
+    }
+}
+
+/// Outbound transfer, its id is equal to the packet id
+#[cw_serde]
+pub struct Transfer {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: String,
+    pub hub_collection: String,
+    pub home_collection: String,
+    pub token_list: Vec<String>,
+    /// is used to mint burned nfts again on refund
+    #[serde(default)]
+    pub metadata_list: Vec<TokenMetadata>,
+    /// coin used to deliver the packet, it's returned to sender on refund
+    pub carrier: Option<Coin>,
+    /// neutron ack fee and timeout fee are escrowed by the sender,
+    /// the unused one is returned to the sender on ack or timeout
+    pub ibc_fee: Option<IbcFee>,
+    /// collection fee is escrowed until the transfer is delivered, it's sent to the admin on
+    /// delivery and returned to the sender on refund
+    #[serde(default)]
+    pub fee: Option<Coin>,
+    /// block time of sending, refunded tokens are removed from the daily usage of that day
+    #[serde(default)]
+    pub timestamp: Timestamp,
+    /// source channel, isn't specified for transfers on the same chain
+    pub channel: Option<String>,
+    /// is received on reply after sending ibc transfer
+    pub sequence: Option<u64>,
+    pub status: TransferStatus,
+}
+
+/// Admin action sent by the hub to outposts
+#[cw_serde]
+pub enum ControlAction {
+    Pause {},
+    Unpause {},
+    AddCollection {
+        hub_collection: String,
+        home_collection: String,
+    },
+    RemoveCollection {
+        hub_collection: String,
     },
-    Wasm {
-        contract: String,
-        msg: M,
+    UpdateConfig {
+        nft_minter: Option<String>,
+        hub_address: Option<String>,
+        token_limit: Option<u8>,
+        min_ntrn_ibc_fee: Option<Uint128>,
     },
 }
 
+#[cw_serde]
+pub struct ControlPacket {
+    /// shares the counter with transfer packets, it's used for replay protection
+    pub id: u64,
+    pub sender: String,
+    pub action: ControlAction,
+}
+
+#[cw_serde]
+pub enum ControlStatus {
+    Pending,
+    Applied,
+    Failed,
+}
+
+/// Control packet sent by the hub, its id is equal to the packet id
+#[cw_serde]
+pub struct Control {
+    pub id: u64,
+    pub prefix: String,
+    pub action: ControlAction,
+    /// coin used to deliver the packet, it's returned to admin on failure
+    pub carrier: Coin,
+    /// neutron ack fee and timeout fee are escrowed by the admin,
+    /// the unused one is returned to the admin on ack or timeout
+    #[serde(default)]
+    pub ibc_fee: Option<IbcFee>,
+    pub channel: String,
+    /// is received on reply after sending ibc transfer
+    pub sequence: Option<u64>,
+    pub status: ControlStatus,
+}
+
+/// untrn fees escrowed by neutron fee refunder, only one of them is paid to the relayer
+#[cw_serde]
+pub struct IbcFee {
+    pub ack_fee: Uint128,
+    pub timeout_fee: Uint128,
+}
+
+impl IbcFee {
+    pub fn total(&self) -> Uint128 {
+        self.ack_fee + self.timeout_fee
+    }
+}
+
+#[cw_serde]
+pub struct FeeBalance {
+    /// untrn escrowed by neutron fee refunder for pending transfers
+    pub escrowed: Uint128,
+    /// untrn held by the contract
+    pub balance: Uint128,
+}
+
+/// carrier coin required to send tokens, any denom is accepted if the list is empty
+#[cw_serde]
+pub struct CarrierRequirement {
+    pub denoms: Vec<String>,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum Transport {
+    /// encrypted packet is sent in ibc-hooks memo of ICS-20 transfer
+    #[default]
+    Ics20,
+    /// standard ICS-721 packets are sent over the contract's own IBC channel
+    Ics721,
+    /// packets are sent directly to the counterparty transceiver over its own IBC channel
+    Native,
+}
+
+/// intermediate chain of multi-hop route, packet-forward-middleware forwards transfers
+/// between its channels
+#[cw_serde]
+pub struct Hop {
+    /// is required to get the address forwarding transfers on the intermediate chain
+    pub prefix: String,
+    /// channel on the intermediate chain to the next chain in direction from the hub
+    pub from_hub: String,
+    /// channel on the intermediate chain to the next chain in direction to the hub
+    pub to_hub: String,
+}
+
 #[cw_serde]
 pub struct Channel {
     pub prefix: String,
     pub from_hub: String,
     pub to_hub: String,
+    #[serde(default)]
+    pub transport: Transport,
+    /// hops ordered from the hub to the outpost, empty for direct channel
+    #[serde(default)]
+    pub route: Vec<Hop>,
+    pub chain_id: Option<String>,
+    /// default relative ibc timeout in seconds, IBC_TIMEOUT is used if it isn't specified
+    pub timeout: Option<u64>,
+    /// carrier denoms accepted by the outpost, any denom is accepted if the list is empty
+    #[serde(default)]
+    pub carrier_denoms: Vec<String>,
 }
 
 impl Channel {
@@ -66,10 +346,72 @@ impl Channel {
             prefix: prefix.to_string(),
             from_hub: from_hub.to_string(),
             to_hub: to_hub.to_string(),
+            transport: Transport::default(),
+            route: vec![],
+            chain_id: None,
+            timeout: None,
+            carrier_denoms: vec![],
+        }
+    }
+
+    pub fn with_transport(self, transport: Transport) -> Self {
+        Self { transport, ..self }
+    }
+
+    pub fn with_route(self, route: Vec<Hop>) -> Self {
+        Self { route, ..self }
+    }
+
+    pub fn with_chain_id(self, chain_id: Option<String>) -> Self {
+        Self { chain_id, ..self }
+    }
+
+    pub fn with_timeout(self, timeout: Option<u64>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    pub fn with_carrier_denoms(self, carrier_denoms: Vec<String>) -> Self {
+        Self {
+            carrier_denoms,
+            ..self
         }
     }
 }
 
+// https://github.com/cosmos/ibc/tree/main/spec/app/ics-721-nft-transfer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[schemars(crate = "::cosmwasm_schema::schemars")]
+#[serde(rename_all = "camelCase")]
+pub struct NonFungibleTokenPacketData {
+    pub class_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_data: Option<Binary>,
+    pub token_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_uris: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_data: Option<Vec<Binary>>,
+    pub sender: String,
+    pub receiver: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// memo of ICS-721 packets sent by the contract, it's used to find the transfer on ack/timeout
+#[cw_serde]
+pub struct Ics721Memo {
+    pub transfer_id: u64,
+}
+
+/// ICS-4 acknowledgement used by both ICS-721 and native transport
+#[cw_serde]
+pub enum IbcAck {
+    Result(Binary),
+    Error(String),
+}
+
 // https://github.com/neutron-org/neutron-sdk/blob/main/packages/neutron-sdk/src/sudo/msg.rs
 #[cw_serde]
 pub struct RequestPacket {
@@ -89,6 +431,46 @@ pub struct RequestPacketTimeoutHeight {
     pub revision_height: Option<u64>,
 }
 
+// https://github.com/neutron-org/neutron/blob/main/proto/neutron/feerefunder/query.proto
+#[cw_serde]
+pub struct FeeRefunderParamsResponse {
+    pub params: FeeRefunderParams,
+}
+
+#[cw_serde]
+pub struct FeeRefunderParams {
+    pub min_fee: FeeRefunderFee,
+}
+
+// https://github.com/neutron-org/neutron/blob/main/proto/neutron/feerefunder/fee.proto
+#[cw_serde]
+pub struct FeeRefunderFee {
+    pub recv_fee: Vec<Coin>,
+    pub ack_fee: Vec<Coin>,
+    pub timeout_fee: Vec<Coin>,
+}
+
+// https://github.com/osmosis-labs/osmosis/blob/main/cosmwasm/packages/registry/src/msg.rs
+#[cw_serde]
+pub enum IbcLifecycleComplete {
+    IbcAck {
+        /// The source channel (sender side) of the IBC packet
+        channel: String,
+        /// The sequence number that the packet was sent with
+        sequence: u64,
+        /// String encoded version of the ack as seen by OnAcknowledgementPacket(..)
+        ack: String,
+        /// Whether an ack is a success or failure according to the transfer spec
+        success: bool,
+    },
+    IbcTimeout {
+        /// The source channel (sender side) of the IBC packet
+        channel: String,
+        /// The sequence number that the packet was sent with
+        sequence: u64,
+    },
+}
+
 /// Height is used for sudo call for `TxQueryResult` enum variant type
 #[cw_serde]
 pub struct Height {
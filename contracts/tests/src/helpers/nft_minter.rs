@@ -5,7 +5,7 @@ use snb_base::{
     error::parse_err,
     nft_minter::{
         msg::{ExecuteMsg, QueryMsg},
-        types::Config,
+        types::{CollectionInfo, Config, TokenMetadata},
     },
 };
 
@@ -22,12 +22,37 @@ pub trait NftMinterExtension {
         sender: ProjectAccount,
         admin: &Option<ProjectAccount>,
         wrapper: Option<&Addr>,
+        cw721_metadata_onchain_code_id: Option<u64>,
+        sg721_code_id: Option<u64>,
+        cw2981_code_id: Option<u64>,
     ) -> StdResult<AppResponse>;
 
     fn nft_minter_try_create_collection(
         &mut self,
         sender: ProjectAccount,
         name: &str,
+        minter: impl ToString,
+    ) -> StdResult<AppResponse>;
+
+    fn nft_minter_try_register_collection(
+        &mut self,
+        sender: ProjectAccount,
+        address: impl ToString,
+        minter: impl ToString,
+    ) -> StdResult<AppResponse>;
+
+    fn nft_minter_try_grant_minter(
+        &mut self,
+        sender: ProjectAccount,
+        collection: impl ToString,
+        minter: impl ToString,
+    ) -> StdResult<AppResponse>;
+
+    fn nft_minter_try_revoke_minter(
+        &mut self,
+        sender: ProjectAccount,
+        collection: impl ToString,
+        minter: impl ToString,
     ) -> StdResult<AppResponse>;
 
     fn nft_minter_try_mint(
@@ -36,6 +61,7 @@ pub trait NftMinterExtension {
         collection: impl ToString,
         token_list: &[&str],
         recipient: impl ToString,
+        metadata_list: Option<&[TokenMetadata]>,
     ) -> StdResult<AppResponse>;
 
     fn nft_minter_try_burn(
@@ -47,13 +73,15 @@ pub trait NftMinterExtension {
 
     fn nft_minter_query_config(&self) -> StdResult<Config>;
 
-    fn nft_minter_query_collection(&self, address: impl ToString) -> StdResult<String>;
+    fn nft_minter_query_collection(&self, address: impl ToString) -> StdResult<CollectionInfo>;
 
     fn nft_minter_query_collection_list(
         &self,
         amount: u32,
         start_after: Option<&str>,
-    ) -> StdResult<Vec<(Addr, String)>>;
+    ) -> StdResult<Vec<(Addr, CollectionInfo)>>;
+
+    fn nft_minter_query_minters(&self, collection: impl ToString) -> StdResult<Vec<Addr>>;
 }
 
 impl NftMinterExtension for Project {
@@ -78,6 +106,9 @@ impl NftMinterExtension for Project {
         sender: ProjectAccount,
         admin: &Option<ProjectAccount>,
         wrapper: Option<&Addr>,
+        cw721_metadata_onchain_code_id: Option<u64>,
+        sg721_code_id: Option<u64>,
+        cw2981_code_id: Option<u64>,
     ) -> StdResult<AppResponse> {
         self.app
             .execute_contract(
@@ -86,6 +117,9 @@ impl NftMinterExtension for Project {
                 &ExecuteMsg::UpdateConfig {
                     admin: admin.map(|x| x.to_string()),
                     wrapper: wrapper.map(|x| x.to_string()),
+                    cw721_metadata_onchain_code_id,
+                    sg721_code_id,
+                    cw2981_code_id,
                 },
                 &[],
             )
@@ -97,6 +131,7 @@ impl NftMinterExtension for Project {
         &mut self,
         sender: ProjectAccount,
         name: &str,
+        minter: impl ToString,
     ) -> StdResult<AppResponse> {
         self.app
             .execute_contract(
@@ -104,6 +139,75 @@ impl NftMinterExtension for Project {
                 self.get_nft_minter_address(),
                 &ExecuteMsg::CreateCollection {
                     name: name.to_string(),
+                    symbol: None,
+                    description: None,
+                    image: None,
+                    external_link: None,
+                    label: None,
+                    flavor: None,
+                    royalty_info: None,
+                    minter: Some(minter.to_string()),
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn nft_minter_try_register_collection(
+        &mut self,
+        sender: ProjectAccount,
+        address: impl ToString,
+        minter: impl ToString,
+    ) -> StdResult<AppResponse> {
+        self.app
+            .execute_contract(
+                sender.into(),
+                self.get_nft_minter_address(),
+                &ExecuteMsg::RegisterCollection {
+                    address: address.to_string(),
+                    label: None,
+                    minter: Some(minter.to_string()),
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn nft_minter_try_grant_minter(
+        &mut self,
+        sender: ProjectAccount,
+        collection: impl ToString,
+        minter: impl ToString,
+    ) -> StdResult<AppResponse> {
+        self.app
+            .execute_contract(
+                sender.into(),
+                self.get_nft_minter_address(),
+                &ExecuteMsg::GrantMinter {
+                    collection: collection.to_string(),
+                    minter: minter.to_string(),
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn nft_minter_try_revoke_minter(
+        &mut self,
+        sender: ProjectAccount,
+        collection: impl ToString,
+        minter: impl ToString,
+    ) -> StdResult<AppResponse> {
+        self.app
+            .execute_contract(
+                sender.into(),
+                self.get_nft_minter_address(),
+                &ExecuteMsg::RevokeMinter {
+                    collection: collection.to_string(),
+                    minter: minter.to_string(),
                 },
                 &[],
             )
@@ -117,6 +221,7 @@ impl NftMinterExtension for Project {
         collection: impl ToString,
         token_list: &[&str],
         recipient: impl ToString,
+        metadata_list: Option<&[TokenMetadata]>,
     ) -> StdResult<AppResponse> {
         self.app
             .execute_contract(
@@ -126,6 +231,7 @@ impl NftMinterExtension for Project {
                     collection: collection.to_string(),
                     token_list: token_list.iter().map(|x| x.to_string()).collect(),
                     recipient: recipient.to_string(),
+                    metadata_list: metadata_list.map(|x| x.to_vec()),
                 },
                 &[],
             )
@@ -160,7 +266,7 @@ impl NftMinterExtension for Project {
     }
 
     #[track_caller]
-    fn nft_minter_query_collection(&self, address: impl ToString) -> StdResult<String> {
+    fn nft_minter_query_collection(&self, address: impl ToString) -> StdResult<CollectionInfo> {
         self.app.wrap().query_wasm_smart(
             self.get_nft_minter_address(),
             &QueryMsg::Collection {
@@ -174,7 +280,7 @@ impl NftMinterExtension for Project {
         &self,
         amount: u32,
         start_after: Option<&str>,
-    ) -> StdResult<Vec<(Addr, String)>> {
+    ) -> StdResult<Vec<(Addr, CollectionInfo)>> {
         self.app.wrap().query_wasm_smart(
             self.get_nft_minter_address(),
             &QueryMsg::CollectionList {
@@ -183,4 +289,14 @@ impl NftMinterExtension for Project {
             },
         )
     }
+
+    #[track_caller]
+    fn nft_minter_query_minters(&self, collection: impl ToString) -> StdResult<Vec<Addr>> {
+        self.app.wrap().query_wasm_smart(
+            self.get_nft_minter_address(),
+            &QueryMsg::Minters {
+                collection: collection.to_string(),
+            },
+        )
+    }
 }
@@ -1,20 +1,34 @@
-use cosmwasm_std::{Addr, StdResult, Uint128};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdResult, Uint128,
+};
 use cw_multi_test::{AppResponse, ContractWrapper, Executor};
+use cw_storage_plus::Item;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 
-use snb_base::{error::parse_err, transceiver::types::TransceiverType};
+use snb_base::{
+    error::parse_err,
+    nft_minter::types::{
+        Cw2981CheckRoyaltiesResponse, Cw2981Metadata, Cw2981QueryMsg, Cw2981RoyaltiesInfoResponse,
+        Metadata, Sg721CollectionInfo, Sg721InstantiateMsg, Sg721QueryMsg,
+    },
+    transceiver::types::TransceiverType,
+};
 
 use crate::helpers::suite::{
     core::Project,
-    types::{GetDecimals, ProjectAccount, ProjectToken},
+    types::{GetDecimals, ProjectAccount, ProjectToken, ENC_KEY},
 };
 
 pub trait WithCodes {
     // store packages
     fn store_cw20_base_code(&mut self) -> u64;
     fn store_cw721_base_code(&mut self) -> u64;
+    fn store_cw721_metadata_onchain_code(&mut self) -> u64;
+    fn store_sg721_code(&mut self) -> u64;
+    fn store_cw2981_code(&mut self) -> u64;
 
     // store contracts
     fn store_nft_minter_code(&mut self) -> u64;
@@ -80,6 +94,30 @@ impl WithCodes for Project {
         )))
     }
 
+    fn store_cw721_metadata_onchain_code(&mut self) -> u64 {
+        self.app.store_code(Box::new(ContractWrapper::new(
+            cw721_metadata_onchain_execute,
+            cw721_metadata_onchain_instantiate,
+            cw721_metadata_onchain_query,
+        )))
+    }
+
+    fn store_sg721_code(&mut self) -> u64 {
+        self.app.store_code(Box::new(ContractWrapper::new(
+            sg721_execute,
+            sg721_instantiate,
+            sg721_query,
+        )))
+    }
+
+    fn store_cw2981_code(&mut self) -> u64 {
+        self.app.store_code(Box::new(ContractWrapper::new(
+            cw2981_execute,
+            cw2981_instantiate,
+            cw2981_query,
+        )))
+    }
+
     // store contracts
     fn store_nft_minter_code(&mut self) -> u64 {
         self.app.store_code(Box::new(
@@ -100,6 +138,8 @@ impl WithCodes for Project {
                 transceiver::contract::instantiate,
                 transceiver::contract::query,
             )
+            .with_reply(transceiver::contract::reply)
+            .with_sudo(transceiver::contract::sudo)
             .with_migrate(transceiver::contract::migrate),
         ))
     }
@@ -196,6 +236,7 @@ impl WithCodes for Project {
                 transceiver_type,
                 token_limit,
                 min_ntrn_ibc_fee: min_ntrn_ibc_fee.map(Uint128::new),
+                enc_key: ENC_KEY.to_string(),
             },
         )
     }
@@ -235,3 +276,135 @@ impl WithCodes for Project {
             .map_err(parse_err)
     }
 }
+
+type Cw721MetadataOnchain<'a> =
+    cw721_base::Cw721Contract<'a, Option<Metadata>, Empty, Empty, Empty>;
+type Sg721<'a> = cw721_base::Cw721Contract<'a, Metadata, Empty, Empty, Empty>;
+type Cw2981<'a> = cw721_base::Cw721Contract<'a, Option<Cw2981Metadata>, Empty, Empty, Empty>;
+
+const SG721_COLLECTION_INFO: Item<Sg721CollectionInfo> = Item::new("collection_info");
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Sg721MockQueryMsg {
+    Sg721(Sg721QueryMsg),
+    Cw721(cw721_base::QueryMsg<Empty>),
+}
+
+fn cw721_metadata_onchain_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Option<Metadata>, Empty>,
+) -> Result<Response, cw721_base::ContractError> {
+    Cw721MetadataOnchain::default().execute(deps, env, info, msg)
+}
+
+fn cw721_metadata_onchain_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::msg::InstantiateMsg,
+) -> StdResult<Response> {
+    Cw721MetadataOnchain::default().instantiate(deps, env, info, msg)
+}
+
+fn cw721_metadata_onchain_query(
+    deps: Deps,
+    env: Env,
+    msg: cw721_base::QueryMsg<Empty>,
+) -> StdResult<Binary> {
+    Cw721MetadataOnchain::default().query(deps, env, msg)
+}
+
+fn sg721_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Metadata, Empty>,
+) -> Result<Response, cw721_base::ContractError> {
+    Sg721::default().execute(deps, env, info, msg)
+}
+
+/// sg721 mock accepting sg721 instantiate msg, only collection info query is supported
+fn sg721_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Sg721InstantiateMsg,
+) -> StdResult<Response> {
+    SG721_COLLECTION_INFO.save(deps.storage, &msg.collection_info)?;
+
+    Sg721::default().instantiate(
+        deps,
+        env,
+        info,
+        cw721_base::msg::InstantiateMsg {
+            name: msg.name,
+            symbol: msg.symbol,
+            minter: msg.minter,
+        },
+    )
+}
+
+fn sg721_query(deps: Deps, env: Env, msg: Sg721MockQueryMsg) -> StdResult<Binary> {
+    match msg {
+        Sg721MockQueryMsg::Sg721(Sg721QueryMsg::CollectionInfo {}) => {
+            to_json_binary(&SG721_COLLECTION_INFO.load(deps.storage)?)
+        }
+        Sg721MockQueryMsg::Cw721(msg) => Sg721::default().query(deps, env, msg),
+    }
+}
+
+fn cw2981_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Option<Cw2981Metadata>, Empty>,
+) -> Result<Response, cw721_base::ContractError> {
+    Cw2981::default().execute(deps, env, info, msg)
+}
+
+fn cw2981_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::msg::InstantiateMsg,
+) -> StdResult<Response> {
+    Cw2981::default().instantiate(deps, env, info, msg)
+}
+
+/// royalty queries are handled like in cw2981-royalties
+fn cw2981_query(
+    deps: Deps,
+    env: Env,
+    msg: cw721_base::QueryMsg<Cw2981QueryMsg>,
+) -> StdResult<Binary> {
+    match msg {
+        cw721_base::QueryMsg::Extension {
+            msg:
+                Cw2981QueryMsg::RoyaltyInfo {
+                    token_id,
+                    sale_price,
+                },
+        } => {
+            let extension = Cw2981::default()
+                .tokens
+                .load(deps.storage, &token_id)?
+                .extension
+                .unwrap_or_default();
+
+            to_json_binary(&Cw2981RoyaltiesInfoResponse {
+                address: extension.royalty_payment_address.unwrap_or_default(),
+                royalty_amount: sale_price
+                    * Decimal::percent(extension.royalty_percentage.unwrap_or_default()),
+            })
+        }
+        cw721_base::QueryMsg::Extension {
+            msg: Cw2981QueryMsg::CheckRoyalties {},
+        } => to_json_binary(&Cw2981CheckRoyaltiesResponse {
+            royalty_payments: true,
+        }),
+        msg => Cw2981::default().query(deps, env, from_json(to_json_binary(&msg)?)?),
+    }
+}
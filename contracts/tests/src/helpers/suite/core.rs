@@ -183,6 +183,22 @@ impl Project {
                 None,
                 None,
                 None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        project
+            .transceiver_try_register_outpost(
+                ProjectAccount::Admin,
+                TransceiverType::Hub,
+                &project.get_transceiver_outpost_address(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
             )
             .unwrap();
 
@@ -195,6 +211,8 @@ impl Project {
                 Some(&project.get_transceiver_hub_address()),
                 None,
                 None,
+                None,
+                None,
             )
             .unwrap();
 
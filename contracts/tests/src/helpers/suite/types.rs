@@ -15,6 +15,8 @@ pub const INCREASED_FUNDS_AMOUNT: u128 = 100 * P12; // give admin such amount of
 pub const DEFAULT_DECIMALS: u8 = 6;
 pub const INCREASED_DECIMALS: u8 = 18;
 
+pub const ENC_KEY: &str = "qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq";
+
 pub type CustomApp = App<
     BankKeeper,
     MockApiBech32,
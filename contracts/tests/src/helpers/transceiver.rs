@@ -1,18 +1,28 @@
-use cosmwasm_std::{Addr, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{
+    testing::{mock_env, mock_ibc_channel, mock_info, MockApi, MockStorage},
+    Addr, Coin, Env, IbcChannelConnectMsg, IbcOrder, OwnedDeps, Querier, StdResult, Uint128,
+};
 use cw_multi_test::{AppResponse, Executor};
 
 use snb_base::{
     error::parse_err,
-    transceiver::types::{Channel, Collection, TransceiverType},
+    nft_minter::types::CollectionFlavor,
+    private_communication::types::EncryptedResponse,
+    transceiver::types::{
+        Channel, Collection, Hop, Outpost, TimeoutBounds, TransceiverType, Transfer, Transport,
+    },
     transceiver::{
-        msg::{ExecuteMsg, QueryMsg},
+        msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+        state::{ICS721_VERSION, NATIVE_VERSION},
         types::Config,
     },
 };
 
+use transceiver::contract;
+
 use crate::helpers::suite::{
     core::{add_funds_to_exec_msg, Project},
-    types::{ProjectAccount, ProjectAsset},
+    types::{ProjectAccount, ProjectAsset, ENC_KEY},
 };
 
 use super::suite::core::to_string_vec;
@@ -46,6 +56,29 @@ pub trait TransceiverExtension {
         hub_address: Option<&Addr>,
         token_limit: Option<u8>,
         min_ntrn_ibc_fee: Option<u128>,
+        hub_control: Option<bool>,
+        timeout_bounds: Option<TimeoutBounds>,
+    ) -> StdResult<AppResponse>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn transceiver_try_register_outpost(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        address: &Addr,
+        from_hub: Option<&str>,
+        to_hub: Option<&str>,
+        transport: Option<Transport>,
+        route: Option<Vec<Hop>>,
+        chain_id: Option<&str>,
+        timeout: Option<u64>,
+    ) -> StdResult<AppResponse>;
+
+    fn transceiver_try_remove_outpost(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        prefix: &str,
     ) -> StdResult<AppResponse>;
 
     fn transceiver_try_add_collection(
@@ -63,6 +96,30 @@ pub trait TransceiverExtension {
         hub_collection: impl ToString,
     ) -> StdResult<AppResponse>;
 
+    fn transceiver_try_create_collection(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        home_collection: impl ToString,
+        name: Option<&str>,
+        symbol: Option<&str>,
+        flavor: Option<CollectionFlavor>,
+    ) -> StdResult<AppResponse>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn transceiver_try_update_collection(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        hub_collection: impl ToString,
+        token_limit: Option<u8>,
+        is_paused: Option<bool>,
+        fee: Option<Coin>,
+        daily_quota: Option<u32>,
+        royalty_recipient: Option<&str>,
+    ) -> StdResult<AppResponse>;
+
+    #[allow(clippy::too_many_arguments)]
     fn transceiver_try_set_channel(
         &mut self,
         sender: ProjectAccount,
@@ -70,6 +127,24 @@ pub trait TransceiverExtension {
         prefix: &str,
         from_hub: &str,
         to_hub: &str,
+        transport: Option<Transport>,
+        route: Option<Vec<Hop>>,
+        chain_id: Option<&str>,
+        timeout: Option<u64>,
+    ) -> StdResult<AppResponse>;
+
+    fn transceiver_try_remove_channel(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        prefix: &str,
+    ) -> StdResult<AppResponse>;
+
+    fn transceiver_try_rotate_key(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        enc_key: &str,
     ) -> StdResult<AppResponse>;
 
     #[allow(clippy::too_many_arguments)]
@@ -80,23 +155,35 @@ pub trait TransceiverExtension {
         hub_collection: impl ToString,
         token_list: &[&str],
         target: Option<Addr>,
+        timeout: Option<u64>,
         amount: u128,
         asset: impl Into<ProjectAsset>,
     ) -> StdResult<AppResponse>;
 
     fn transceiver_try_accept(
         &mut self,
-        sender: ProjectAccount,
+        sender: impl Into<Addr>,
+        transceiver: TransceiverType,
+        encrypted_response: &EncryptedResponse,
+    ) -> StdResult<AppResponse>;
+
+    fn transceiver_try_control(
+        &mut self,
+        sender: impl Into<Addr>,
         transceiver: TransceiverType,
-        msg: &str,
-        timestamp: Timestamp,
+        encrypted_response: &EncryptedResponse,
     ) -> StdResult<AppResponse>;
 
     fn transceiver_query_config(&self, transceiver: TransceiverType) -> StdResult<Config>;
 
     fn transceiver_query_pause_state(&self, transceiver: TransceiverType) -> StdResult<bool>;
 
-    fn transceiver_query_outposts(&self, transceiver: TransceiverType) -> StdResult<Vec<String>>;
+    fn transceiver_query_outposts(
+        &self,
+        transceiver: TransceiverType,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Outpost>>;
 
     fn transceiver_query_collection(
         &self,
@@ -108,12 +195,44 @@ pub trait TransceiverExtension {
     fn transceiver_query_collection_list(
         &self,
         transceiver: TransceiverType,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
     ) -> StdResult<Vec<Collection>>;
 
     fn transceiver_query_channel_list(
         &self,
         transceiver: TransceiverType,
+        start_after: Option<&str>,
+        limit: Option<u32>,
     ) -> StdResult<Vec<Channel>>;
+
+    fn transceiver_query_packet_state(
+        &self,
+        transceiver: TransceiverType,
+        sender: impl ToString,
+        packet_id: u64,
+    ) -> StdResult<bool>;
+
+    fn transceiver_query_transfer(
+        &self,
+        transceiver: TransceiverType,
+        id: u64,
+    ) -> StdResult<Transfer>;
+
+    fn transceiver_query_transfers_by_sender(
+        &self,
+        transceiver: TransceiverType,
+        sender: impl ToString,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Transfer>>;
+
+    fn transceiver_query_pending_transfers(
+        &self,
+        transceiver: TransceiverType,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Transfer>>;
 }
 
 impl TransceiverExtension for Project {
@@ -190,6 +309,8 @@ impl TransceiverExtension for Project {
         hub_address: Option<&Addr>,
         token_limit: Option<u8>,
         min_ntrn_ibc_fee: Option<u128>,
+        hub_control: Option<bool>,
+        timeout_bounds: Option<TimeoutBounds>,
     ) -> StdResult<AppResponse> {
         let transceiver_address = match transceiver {
             TransceiverType::Hub => self.get_transceiver_hub_address(),
@@ -206,6 +327,68 @@ impl TransceiverExtension for Project {
                     hub_address: hub_address.map(|x| x.to_string()),
                     token_limit,
                     min_ntrn_ibc_fee: min_ntrn_ibc_fee.map(Uint128::new),
+                    hub_control,
+                    timeout_bounds,
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn transceiver_try_register_outpost(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        address: &Addr,
+        from_hub: Option<&str>,
+        to_hub: Option<&str>,
+        transport: Option<Transport>,
+        route: Option<Vec<Hop>>,
+        chain_id: Option<&str>,
+        timeout: Option<u64>,
+    ) -> StdResult<AppResponse> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app
+            .execute_contract(
+                sender.into(),
+                transceiver_address,
+                &ExecuteMsg::RegisterOutpost {
+                    address: address.to_string(),
+                    from_hub: from_hub.map(|x| x.to_string()),
+                    to_hub: to_hub.map(|x| x.to_string()),
+                    transport,
+                    route,
+                    chain_id: chain_id.map(|x| x.to_string()),
+                    timeout,
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn transceiver_try_remove_outpost(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        prefix: &str,
+    ) -> StdResult<AppResponse> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app
+            .execute_contract(
+                sender.into(),
+                transceiver_address,
+                &ExecuteMsg::RemoveOutpost {
+                    prefix: prefix.to_string(),
                 },
                 &[],
             )
@@ -262,6 +445,70 @@ impl TransceiverExtension for Project {
             .map_err(parse_err)
     }
 
+    #[track_caller]
+    fn transceiver_try_create_collection(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        home_collection: impl ToString,
+        name: Option<&str>,
+        symbol: Option<&str>,
+        flavor: Option<CollectionFlavor>,
+    ) -> StdResult<AppResponse> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app
+            .execute_contract(
+                sender.into(),
+                transceiver_address,
+                &ExecuteMsg::CreateCollection {
+                    home_collection: home_collection.to_string(),
+                    name: name.map(|x| x.to_string()),
+                    symbol: symbol.map(|x| x.to_string()),
+                    flavor,
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn transceiver_try_update_collection(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        hub_collection: impl ToString,
+        token_limit: Option<u8>,
+        is_paused: Option<bool>,
+        fee: Option<Coin>,
+        daily_quota: Option<u32>,
+        royalty_recipient: Option<&str>,
+    ) -> StdResult<AppResponse> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app
+            .execute_contract(
+                sender.into(),
+                transceiver_address,
+                &ExecuteMsg::UpdateCollection {
+                    hub_collection: hub_collection.to_string(),
+                    token_limit,
+                    is_paused,
+                    fee,
+                    daily_quota,
+                    royalty_recipient: royalty_recipient.map(|x| x.to_string()),
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
     #[track_caller]
     fn transceiver_try_set_channel(
         &mut self,
@@ -270,6 +517,10 @@ impl TransceiverExtension for Project {
         prefix: &str,
         from_hub: &str,
         to_hub: &str,
+        transport: Option<Transport>,
+        route: Option<Vec<Hop>>,
+        chain_id: Option<&str>,
+        timeout: Option<u64>,
     ) -> StdResult<AppResponse> {
         let transceiver_address = match transceiver {
             TransceiverType::Hub => self.get_transceiver_hub_address(),
@@ -284,6 +535,58 @@ impl TransceiverExtension for Project {
                     prefix: prefix.to_string(),
                     from_hub: from_hub.to_string(),
                     to_hub: to_hub.to_string(),
+                    transport,
+                    route,
+                    chain_id: chain_id.map(|x| x.to_string()),
+                    timeout,
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn transceiver_try_remove_channel(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        prefix: &str,
+    ) -> StdResult<AppResponse> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app
+            .execute_contract(
+                sender.into(),
+                transceiver_address,
+                &ExecuteMsg::RemoveChannel {
+                    prefix: prefix.to_string(),
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn transceiver_try_rotate_key(
+        &mut self,
+        sender: ProjectAccount,
+        transceiver: TransceiverType,
+        enc_key: &str,
+    ) -> StdResult<AppResponse> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app
+            .execute_contract(
+                sender.into(),
+                transceiver_address,
+                &ExecuteMsg::RotateKey {
+                    enc_key: enc_key.to_string(),
                 },
                 &[],
             )
@@ -298,6 +601,7 @@ impl TransceiverExtension for Project {
         hub_collection: impl ToString,
         token_list: &[&str],
         target: Option<Addr>,
+        timeout: Option<u64>,
         amount: u128,
         asset: impl Into<ProjectAsset>,
     ) -> StdResult<AppResponse> {
@@ -314,6 +618,7 @@ impl TransceiverExtension for Project {
                 hub_collection: hub_collection.to_string(),
                 token_list: to_string_vec(token_list),
                 target: target.map(|x| x.to_string()),
+                timeout,
             },
             amount,
             asset,
@@ -323,10 +628,9 @@ impl TransceiverExtension for Project {
     #[track_caller]
     fn transceiver_try_accept(
         &mut self,
-        sender: ProjectAccount,
+        sender: impl Into<Addr>,
         transceiver: TransceiverType,
-        msg: &str,
-        timestamp: Timestamp,
+        encrypted_response: &EncryptedResponse,
     ) -> StdResult<AppResponse> {
         let transceiver_address = match transceiver {
             TransceiverType::Hub => self.get_transceiver_hub_address(),
@@ -338,8 +642,37 @@ impl TransceiverExtension for Project {
                 sender.into(),
                 transceiver_address,
                 &ExecuteMsg::Accept {
-                    msg: msg.to_string(),
-                    timestamp,
+                    msg: encrypted_response.value.to_string(),
+                    timestamp: encrypted_response.timestamp,
+                    key_id: encrypted_response.key_id,
+                    nonce: encrypted_response.nonce.to_owned(),
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    }
+
+    #[track_caller]
+    fn transceiver_try_control(
+        &mut self,
+        sender: impl Into<Addr>,
+        transceiver: TransceiverType,
+        encrypted_response: &EncryptedResponse,
+    ) -> StdResult<AppResponse> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app
+            .execute_contract(
+                sender.into(),
+                transceiver_address,
+                &ExecuteMsg::Control {
+                    msg: encrypted_response.value.to_string(),
+                    timestamp: encrypted_response.timestamp,
+                    key_id: encrypted_response.key_id,
+                    nonce: encrypted_response.nonce.to_owned(),
                 },
                 &[],
             )
@@ -371,15 +704,24 @@ impl TransceiverExtension for Project {
     }
 
     #[track_caller]
-    fn transceiver_query_outposts(&self, transceiver: TransceiverType) -> StdResult<Vec<String>> {
+    fn transceiver_query_outposts(
+        &self,
+        transceiver: TransceiverType,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Outpost>> {
         let transceiver_address = match transceiver {
             TransceiverType::Hub => self.get_transceiver_hub_address(),
             TransceiverType::Outpost => self.get_transceiver_outpost_address(),
         };
 
-        self.app
-            .wrap()
-            .query_wasm_smart(transceiver_address, &QueryMsg::Outposts {})
+        self.app.wrap().query_wasm_smart(
+            transceiver_address,
+            &QueryMsg::Outposts {
+                start_after: start_after.map(|x| x.to_string()),
+                limit,
+            },
+        )
     }
 
     #[track_caller]
@@ -407,29 +749,219 @@ impl TransceiverExtension for Project {
     fn transceiver_query_collection_list(
         &self,
         transceiver: TransceiverType,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
     ) -> StdResult<Vec<Collection>> {
         let transceiver_address = match transceiver {
             TransceiverType::Hub => self.get_transceiver_hub_address(),
             TransceiverType::Outpost => self.get_transceiver_outpost_address(),
         };
 
-        self.app
-            .wrap()
-            .query_wasm_smart(transceiver_address, &QueryMsg::CollectionList {})
+        self.app.wrap().query_wasm_smart(
+            transceiver_address,
+            &QueryMsg::CollectionList {
+                start_after: start_after.map(|x| x.to_string()),
+                limit,
+            },
+        )
     }
 
     #[track_caller]
     fn transceiver_query_channel_list(
         &self,
         transceiver: TransceiverType,
+        start_after: Option<&str>,
+        limit: Option<u32>,
     ) -> StdResult<Vec<Channel>> {
         let transceiver_address = match transceiver {
             TransceiverType::Hub => self.get_transceiver_hub_address(),
             TransceiverType::Outpost => self.get_transceiver_outpost_address(),
         };
 
+        self.app.wrap().query_wasm_smart(
+            transceiver_address,
+            &QueryMsg::ChannelList {
+                start_after: start_after.map(|x| x.to_string()),
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    fn transceiver_query_packet_state(
+        &self,
+        transceiver: TransceiverType,
+        sender: impl ToString,
+        packet_id: u64,
+    ) -> StdResult<bool> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app.wrap().query_wasm_smart(
+            transceiver_address,
+            &QueryMsg::PacketState {
+                sender: sender.to_string(),
+                packet_id,
+            },
+        )
+    }
+
+    #[track_caller]
+    fn transceiver_query_transfer(
+        &self,
+        transceiver: TransceiverType,
+        id: u64,
+    ) -> StdResult<Transfer> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
         self.app
             .wrap()
-            .query_wasm_smart(transceiver_address, &QueryMsg::ChannelList {})
+            .query_wasm_smart(transceiver_address, &QueryMsg::Transfer { id })
+    }
+
+    #[track_caller]
+    fn transceiver_query_transfers_by_sender(
+        &self,
+        transceiver: TransceiverType,
+        sender: impl ToString,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Transfer>> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app.wrap().query_wasm_smart(
+            transceiver_address,
+            &QueryMsg::TransfersBySender {
+                sender: sender.to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    fn transceiver_query_pending_transfers(
+        &self,
+        transceiver: TransceiverType,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Transfer>> {
+        let transceiver_address = match transceiver {
+            TransceiverType::Hub => self.get_transceiver_hub_address(),
+            TransceiverType::Outpost => self.get_transceiver_outpost_address(),
+        };
+
+        self.app.wrap().query_wasm_smart(
+            transceiver_address,
+            &QueryMsg::PendingTransfers { start_after, limit },
+        )
+    }
+}
+
+/// counterparty of the transceiver instantiated with mock dependencies
+pub const MOCK_HUB: &str = "neutron1transceiver";
+pub const MOCK_OUTPOST: &str = "stars1transceiver";
+
+/// instantiates the transceiver with mock dependencies and connects it to the counterparty
+/// over channel-0 (from hub) and channel-1 (to hub), the collection pair is added if specified.
+/// the contract's own channel is opened for ICS-721 and native transport. returns (env, admin)
+pub fn mock_transceiver<Q: Querier>(
+    deps: &mut OwnedDeps<MockStorage, MockApi, Q>,
+    transceiver_type: TransceiverType,
+    transport: Option<Transport>,
+    timeout: Option<u64>,
+    collection: Option<(&str, &str)>,
+) -> (Env, Addr) {
+    let mut env = mock_env();
+    env.contract.address = deps.api.addr_make("transceiver");
+    let admin = deps.api.addr_make("admin");
+    let nft_minter = deps.api.addr_make("nft_minter");
+    let execute = |deps: &mut OwnedDeps<MockStorage, MockApi, Q>, msg: ExecuteMsg| {
+        contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(admin.as_str(), &[]),
+            msg,
+        )
+        .unwrap();
+    };
+
+    contract::instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(admin.as_str(), &[]),
+        InstantiateMsg {
+            nft_minter: Some(nft_minter.to_string()),
+            hub_address: Some(MOCK_HUB.to_string()),
+            transceiver_type: transceiver_type.clone(),
+            token_limit: None,
+            min_ntrn_ibc_fee: None,
+            enc_key: ENC_KEY.to_string(),
+        },
+    )
+    .unwrap();
+
+    let (channel_id, counterparty) = match transceiver_type {
+        TransceiverType::Hub => ("channel-0", MOCK_OUTPOST),
+        TransceiverType::Outpost => ("channel-1", MOCK_HUB),
+    };
+    let version = match transport {
+        Some(Transport::Ics721) => Some(ICS721_VERSION),
+        Some(Transport::Native) => Some(NATIVE_VERSION),
+        _ => None,
+    };
+
+    if let Some(version) = version {
+        let mut channel = mock_ibc_channel(channel_id, IbcOrder::Unordered, version);
+        channel.counterparty_endpoint.port_id = format!("wasm.{}", counterparty);
+
+        contract::ibc_channel_connect(
+            deps.as_mut(),
+            env.clone(),
+            IbcChannelConnectMsg::new_ack(channel, version),
+        )
+        .unwrap();
     }
+
+    let msg = match transceiver_type {
+        TransceiverType::Hub => ExecuteMsg::RegisterOutpost {
+            address: MOCK_OUTPOST.to_string(),
+            from_hub: Some("channel-0".to_string()),
+            to_hub: Some("channel-1".to_string()),
+            transport,
+            route: None,
+            chain_id: None,
+            timeout,
+        },
+        TransceiverType::Outpost => ExecuteMsg::SetChannel {
+            prefix: "cosmwasm".to_string(),
+            from_hub: "channel-0".to_string(),
+            to_hub: "channel-1".to_string(),
+            transport,
+            route: None,
+            chain_id: None,
+            timeout,
+        },
+    };
+    execute(deps, msg);
+
+    if let Some((hub_collection, home_collection)) = collection {
+        execute(
+            deps,
+            ExecuteMsg::AddCollection {
+                hub_collection: hub_collection.to_string(),
+                home_collection: home_collection.to_string(),
+            },
+        );
+    }
+
+    (env, admin)
 }
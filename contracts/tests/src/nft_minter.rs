@@ -1,8 +1,29 @@
+use cosmwasm_std::{Addr, Decimal, StdResult};
 use cw_multi_test::Executor;
+use cw_storage_plus::Map;
+use speculoos::{assert_that, prelude::StrAssertions};
 
-use snb_base::nft_minter::msg::MigrateMsg;
+use snb_base::{
+    error::{parse_err, ContractError},
+    nft_minter::{
+        msg::{ExecuteMsg, MigrateMsg},
+        state::MINTERS,
+        types::{
+            CollectionFlavor, CollectionInfo, Metadata, RoyaltyInfo, Sg721CollectionInfo,
+            Sg721InstantiateMsg, TokenMetadata,
+        },
+    },
+};
 
-use crate::helpers::suite::{core::Project, types::ProjectAccount};
+use crate::helpers::{
+    nft_minter::NftMinterExtension,
+    suite::{
+        codes::WithCodes,
+        core::{assert_error, Project},
+        types::{ProjectAccount, ProjectNft},
+    },
+    wrapper::WrapperExtension,
+};
 
 #[test]
 fn migrate_default() {
@@ -19,3 +40,515 @@ fn migrate_default() {
         )
         .unwrap();
 }
+
+#[test]
+fn create_collection_with_metadata() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.app
+        .execute_contract(
+            ProjectAccount::Admin.into(),
+            p.get_nft_minter_address(),
+            &ExecuteMsg::CreateCollection {
+                name: "gopniks".to_string(),
+                symbol: Some("GOP".to_string()),
+                description: Some("Gopniks on Neutron".to_string()),
+                image: Some("ipfs://gopniks.png".to_string()),
+                external_link: Some("https://gopniks.com".to_string()),
+                label: Some("gopniks".to_string()),
+                flavor: None,
+                royalty_info: None,
+                minter: None,
+            },
+            &[],
+        )
+        .unwrap();
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "pinjeons",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let gopniks = collection_list
+        .iter()
+        .find(|(_, x)| x.name == "gopniks")
+        .map(|(address, _)| address.to_owned())
+        .unwrap();
+    let pinjeons = collection_list
+        .iter()
+        .find(|(_, x)| x.name == "pinjeons")
+        .map(|(address, _)| address.to_owned())
+        .unwrap();
+
+    assert_that(&p.nft_minter_query_collection(&gopniks)?).is_equal_to(CollectionInfo {
+        name: "gopniks".to_string(),
+        symbol: "GOP".to_string(),
+        description: Some("Gopniks on Neutron".to_string()),
+        image: Some("ipfs://gopniks.png".to_string()),
+        external_link: Some("https://gopniks.com".to_string()),
+        label: "gopniks".to_string(),
+        flavor: CollectionFlavor::Cw721Base,
+        royalty_info: None,
+    });
+    assert_that(&p.nft_minter_query_collection(&pinjeons)?.label)
+        .is_equal_to("Simple NFT Bridge collection: pinjeons".to_string());
+
+    // minter isn't granted if it isn't specified by the admin
+    assert_that(&p.nft_minter_query_minters(&gopniks)?).is_equal_to(vec![]);
+    assert_that(&p.nft_minter_query_minters(&pinjeons)?)
+        .is_equal_to(vec![p.get_transceiver_hub_address()]);
+
+    // symbol and label are passed to cw721 contract
+    let contract_info: cw721::ContractInfoResponse = p
+        .app
+        .wrap()
+        .query_wasm_smart(&gopniks, &cw721::Cw721QueryMsg::ContractInfo {})?;
+    assert_that(&contract_info.symbol).is_equal_to("GOP".to_string());
+
+    let contract_data = p.app.contract_data(&gopniks).unwrap();
+    assert_that(&contract_data.label).is_equal_to("gopniks".to_string());
+
+    // collection names are replaced with collection info on migration
+    let legacy_collections = Map::<&Addr, String>::new("collections");
+    legacy_collections.save(
+        p.app
+            .contract_storage_mut(&p.get_nft_minter_address())
+            .as_mut(),
+        &gopniks,
+        &"gopniks".to_string(),
+    )?;
+
+    p.app
+        .migrate_contract(
+            ProjectAccount::Admin.into(),
+            p.get_nft_minter_address(),
+            &MigrateMsg {
+                version: "1.1.0".to_string(),
+            },
+            p.get_nft_minter_code_id(),
+        )
+        .unwrap();
+
+    assert_that(&p.nft_minter_query_collection(&gopniks)?).is_equal_to(CollectionInfo {
+        name: "gopniks".to_string(),
+        symbol: "GOP".to_string(),
+        description: None,
+        image: None,
+        external_link: None,
+        label: "Simple NFT Bridge collection: gopniks".to_string(),
+        flavor: CollectionFlavor::Cw721Base,
+        royalty_info: None,
+    });
+    assert_that(&p.nft_minter_query_collection(&pinjeons)?.name)
+        .is_equal_to("pinjeons".to_string());
+
+    Ok(())
+}
+
+#[test]
+fn create_collection_with_flavor() -> StdResult<()> {
+    let mut p = Project::new();
+
+    let create_collection = |p: &mut Project, name: &str, flavor, image: Option<&str>| {
+        p.app
+            .execute_contract(
+                ProjectAccount::Admin.into(),
+                p.get_nft_minter_address(),
+                &ExecuteMsg::CreateCollection {
+                    name: name.to_string(),
+                    symbol: None,
+                    description: None,
+                    image: image.map(|x| x.to_string()),
+                    external_link: None,
+                    label: None,
+                    flavor: Some(flavor),
+                    royalty_info: None,
+                    minter: Some(p.get_transceiver_hub_address().to_string()),
+                },
+                &[],
+            )
+            .map_err(parse_err)
+    };
+
+    // code ids aren't specified by default
+    let res = create_collection(
+        &mut p,
+        "gopniks",
+        CollectionFlavor::Cw721MetadataOnchain,
+        None,
+    )
+    .unwrap_err();
+    assert_error(&res, ContractError::CodeIdIsNotFound);
+
+    let cw721_metadata_onchain_code_id = p.store_cw721_metadata_onchain_code();
+    let sg721_code_id = p.store_sg721_code();
+    p.nft_minter_try_update_config(
+        ProjectAccount::Admin,
+        &None,
+        None,
+        Some(cw721_metadata_onchain_code_id),
+        Some(sg721_code_id),
+        None,
+    )?;
+
+    // sg721 requires collection image
+    let res = create_collection(&mut p, "pinjeons", CollectionFlavor::Sg721, None).unwrap_err();
+    assert_that(&res.to_string()).contains(
+        ContractError::ParameterIsNotFound {
+            value: "image".to_string(),
+        }
+        .to_string()
+        .as_str(),
+    );
+
+    create_collection(
+        &mut p,
+        "gopniks",
+        CollectionFlavor::Cw721MetadataOnchain,
+        None,
+    )?;
+    create_collection(
+        &mut p,
+        "pinjeons",
+        CollectionFlavor::Sg721,
+        Some("ipfs://pinjeons.png"),
+    )?;
+    create_collection(&mut p, "badgers", CollectionFlavor::Cw721Base, None)?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let get_collection = |name: &str| {
+        collection_list
+            .iter()
+            .find(|(_, x)| x.name == name)
+            .map(|(address, _)| address.to_owned())
+            .unwrap()
+    };
+    let gopniks = get_collection("gopniks");
+    let pinjeons = get_collection("pinjeons");
+    let badgers = get_collection("badgers");
+
+    assert_that(&p.nft_minter_query_collection(&pinjeons)?.flavor)
+        .is_equal_to(CollectionFlavor::Sg721);
+    assert_that(&p.app.contract_data(&gopniks).unwrap().code_id)
+        .is_equal_to(cw721_metadata_onchain_code_id);
+    assert_that(&p.app.contract_data(&pinjeons).unwrap().code_id).is_equal_to(sg721_code_id);
+
+    // extension is minted according to collection flavor
+    let metadata = TokenMetadata {
+        token_uri: Some("ipfs://1.json".to_string()),
+        extension: Some(Metadata {
+            name: Some("Gopnik #1".to_string()),
+            ..Metadata::default()
+        }),
+        royalty_info: None,
+    };
+
+    for collection in [&gopniks, &pinjeons, &badgers] {
+        p.app
+            .execute_contract(
+                p.get_transceiver_hub_address(),
+                p.get_nft_minter_address(),
+                &ExecuteMsg::Mint {
+                    collection: collection.to_string(),
+                    token_list: vec!["1".to_string()],
+                    recipient: ProjectAccount::Alice.to_string(),
+                    metadata_list: Some(vec![metadata.clone()]),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    let nft_info: cw721::NftInfoResponse<Option<Metadata>> = p.app.wrap().query_wasm_smart(
+        &gopniks,
+        &cw721::Cw721QueryMsg::NftInfo {
+            token_id: "1".to_string(),
+        },
+    )?;
+    assert_that(&nft_info.token_uri).is_equal_to(metadata.token_uri.clone());
+    assert_that(&nft_info.extension).is_equal_to(metadata.extension.clone());
+
+    let nft_info: cw721::NftInfoResponse<Metadata> = p.app.wrap().query_wasm_smart(
+        &pinjeons,
+        &cw721::Cw721QueryMsg::NftInfo {
+            token_id: "1".to_string(),
+        },
+    )?;
+    assert_that(&Some(nft_info.extension)).is_equal_to(metadata.extension.clone());
+
+    // cw721-base collection keeps token uri only
+    let nft_info: cw721::NftInfoResponse<Option<Metadata>> = p.app.wrap().query_wasm_smart(
+        &badgers,
+        &cw721::Cw721QueryMsg::NftInfo {
+            token_id: "1".to_string(),
+        },
+    )?;
+    assert_that(&nft_info.token_uri).is_equal_to(metadata.token_uri);
+    assert_that(&nft_info.extension).is_equal_to(None);
+
+    Ok(())
+}
+
+#[test]
+fn minter_permissions() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "pinjeons",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let get_collection = |name: &str| {
+        collection_list
+            .iter()
+            .find(|(_, x)| x.name == name)
+            .map(|(address, _)| address.to_owned())
+            .unwrap()
+    };
+    let gopniks = get_collection("gopniks");
+    let pinjeons = get_collection("pinjeons");
+    let transceiver_hub = p.get_transceiver_hub_address();
+    let wrapper = p.get_wrapper_address();
+
+    // specified minter is granted on collection creation
+    for collection in [&gopniks, &pinjeons] {
+        assert_that(&p.nft_minter_query_minters(collection)?)
+            .is_equal_to(vec![transceiver_hub.clone()]);
+    }
+
+    let res = p
+        .nft_minter_try_grant_minter(ProjectAccount::Alice, &pinjeons, &wrapper)
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    let res = p
+        .nft_minter_try_grant_minter(ProjectAccount::Admin, ProjectNft::Gopniks, &wrapper)
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionIsNotFound);
+
+    // pinjeons become wrapped collection
+    p.nft_minter_try_grant_minter(ProjectAccount::Admin, &pinjeons, &wrapper)?;
+    p.nft_minter_try_revoke_minter(ProjectAccount::Admin, &pinjeons, &transceiver_hub)?;
+    assert_that(&p.nft_minter_query_minters(&pinjeons)?).is_equal_to(vec![wrapper.clone()]);
+
+    let res = p
+        .nft_minter_try_revoke_minter(ProjectAccount::Admin, &pinjeons, &transceiver_hub)
+        .unwrap_err();
+    assert_error(&res, ContractError::MinterIsNotFound);
+
+    // minters can't mint in collections of each other
+    for (sender, collection, is_allowed) in [
+        (&transceiver_hub, &gopniks, true),
+        (&transceiver_hub, &pinjeons, false),
+        (&wrapper, &gopniks, false),
+        (&wrapper, &pinjeons, true),
+    ] {
+        let res = p
+            .app
+            .execute_contract(
+                sender.to_owned(),
+                p.get_nft_minter_address(),
+                &ExecuteMsg::Mint {
+                    collection: collection.to_string(),
+                    token_list: vec![sender.to_string()],
+                    recipient: ProjectAccount::Alice.to_string(),
+                    metadata_list: None,
+                },
+                &[],
+            )
+            .map_err(parse_err);
+
+        if is_allowed {
+            res?;
+        } else {
+            assert_error(&res.unwrap_err(), ContractError::Unauthorized);
+        }
+    }
+
+    // minters of legacy collections are assigned on migration
+    p.nft_minter_try_update_config(
+        ProjectAccount::Admin,
+        &None,
+        Some(&wrapper),
+        None,
+        None,
+        None,
+    )?;
+    p.wrapper_try_add_collection(ProjectAccount::Admin, ProjectNft::Gopniks, &pinjeons)?;
+
+    for (collection, minter) in [(&gopniks, &transceiver_hub), (&pinjeons, &wrapper)] {
+        MINTERS.remove(
+            p.app
+                .contract_storage_mut(&p.get_nft_minter_address())
+                .as_mut(),
+            (collection, minter),
+        );
+    }
+
+    p.app
+        .migrate_contract(
+            ProjectAccount::Admin.into(),
+            p.get_nft_minter_address(),
+            &MigrateMsg {
+                version: "1.1.0".to_string(),
+            },
+            p.get_nft_minter_code_id(),
+        )
+        .unwrap();
+
+    assert_that(&p.nft_minter_query_minters(&gopniks)?).is_equal_to(vec![transceiver_hub]);
+    assert_that(&p.nft_minter_query_minters(&pinjeons)?).is_equal_to(vec![wrapper]);
+
+    Ok(())
+}
+
+#[test]
+fn register_collection() -> StdResult<()> {
+    let mut p = Project::new();
+
+    let cw721_metadata_onchain_code_id = p.store_cw721_metadata_onchain_code();
+    let sg721_code_id = p.store_sg721_code();
+    p.nft_minter_try_update_config(
+        ProjectAccount::Admin,
+        &None,
+        None,
+        Some(cw721_metadata_onchain_code_id),
+        Some(sg721_code_id),
+        None,
+    )?;
+
+    // minter of the deployed collection is handed over to nft-minter
+    let gopniks = p.instantiate_contract(
+        cw721_metadata_onchain_code_id,
+        "gopniks",
+        &cw721_base::msg::InstantiateMsg {
+            name: "gopniks".to_string(),
+            symbol: "GOP".to_string(),
+            minter: p.get_nft_minter_address().to_string(),
+        },
+    );
+
+    let res = p
+        .nft_minter_try_register_collection(
+            ProjectAccount::Alice,
+            &gopniks,
+            p.get_transceiver_hub_address(),
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    let res = p
+        .nft_minter_try_register_collection(
+            ProjectAccount::Admin,
+            ProjectNft::Gopniks,
+            p.get_transceiver_hub_address(),
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongCollectionMinter);
+
+    p.nft_minter_try_register_collection(
+        ProjectAccount::Admin,
+        &gopniks,
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let res = p
+        .nft_minter_try_register_collection(
+            ProjectAccount::Admin,
+            &gopniks,
+            p.get_transceiver_hub_address(),
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionDuplication);
+
+    // flavor is defined by code id
+    assert_that(&p.nft_minter_query_collection(&gopniks)?).is_equal_to(CollectionInfo {
+        name: "gopniks".to_string(),
+        symbol: "GOP".to_string(),
+        description: None,
+        image: None,
+        external_link: None,
+        label: "gopniks".to_string(),
+        flavor: CollectionFlavor::Cw721MetadataOnchain,
+        royalty_info: None,
+    });
+    assert_that(&p.nft_minter_query_minters(&gopniks)?)
+        .is_equal_to(vec![p.get_transceiver_hub_address()]);
+
+    // registered collection can be used for bridging
+    p.app
+        .execute_contract(
+            p.get_transceiver_hub_address(),
+            p.get_nft_minter_address(),
+            &ExecuteMsg::Mint {
+                collection: gopniks.to_string(),
+                token_list: vec!["1".to_string()],
+                recipient: ProjectAccount::Alice.to_string(),
+                metadata_list: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let owner: cw721::OwnerOfResponse = p.app.wrap().query_wasm_smart(
+        &gopniks,
+        &cw721::Cw721QueryMsg::OwnerOf {
+            token_id: "1".to_string(),
+            include_expired: None,
+        },
+    )?;
+    assert_that(&owner.owner).is_equal_to(ProjectAccount::Alice.to_string());
+
+    // sg721 collection royalty is imported, the label is the collection name by default
+    let royalty_info = RoyaltyInfo {
+        payment_address: ProjectAccount::Bob.to_string(),
+        share: Decimal::percent(5),
+    };
+    let pinjeons = p.instantiate_contract(
+        sg721_code_id,
+        "pinjeons",
+        &Sg721InstantiateMsg {
+            name: "pinjeons".to_string(),
+            symbol: "PINJ".to_string(),
+            minter: p.get_nft_minter_address().to_string(),
+            collection_info: Sg721CollectionInfo {
+                creator: ProjectAccount::Owner.to_string(),
+                description: String::default(),
+                image: "ipfs://pinjeons.png".to_string(),
+                external_link: None,
+                explicit_content: None,
+                start_trading_time: None,
+                royalty_info: Some(royalty_info.clone()),
+            },
+        },
+    );
+
+    p.nft_minter_try_register_collection(
+        ProjectAccount::Admin,
+        &pinjeons,
+        p.get_wrapper_address(),
+    )?;
+
+    assert_that(&p.nft_minter_query_collection(&pinjeons)?).is_equal_to(CollectionInfo {
+        name: "pinjeons".to_string(),
+        symbol: "PINJ".to_string(),
+        description: None,
+        image: None,
+        external_link: None,
+        label: "pinjeons".to_string(),
+        flavor: CollectionFlavor::Sg721,
+        royalty_info: Some(royalty_info),
+    });
+    // only the specified minter is granted
+    assert_that(&p.nft_minter_query_minters(&pinjeons)?).is_equal_to(vec![p.get_wrapper_address()]);
+
+    Ok(())
+}
@@ -1,17 +1,65 @@
+use std::marker::PhantomData;
+
 use cw_multi_test::Executor;
-use speculoos::assert_that;
+use cw_storage_plus::Item;
+use speculoos::{
+    assert_that,
+    prelude::{ContainingIntoIterAssertions, StrAssertions},
+};
+
+use cosmwasm_std::{
+    coin, coins, from_json,
+    testing::{
+        mock_dependencies, mock_ibc_channel_close_confirm, mock_ibc_packet_ack,
+        mock_ibc_packet_recv, mock_ibc_packet_timeout, mock_info, MockApi, MockQuerier,
+        MockStorage,
+    },
+    to_json_binary, to_json_string, Addr, BankMsg, Binary, Coin, ContractResult, CosmosMsg,
+    Decimal, Deps, DepsMut, Empty, IbcAcknowledgement, IbcMsg, IbcOrder, IbcTimeout, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, Reply, StdResult, SubMsg, SubMsgResponse, SubMsgResult,
+    SystemResult, Timestamp, Uint128, WasmMsg, WasmQuery,
+};
+
+use encryption_helper::serde::serialize_encrypt;
 
-use cosmwasm_std::StdResult;
+use transceiver::{
+    contract,
+    helpers::{get_accept_msg, get_ibc_transfer_memo},
+};
 
-use snb_base::transceiver::{msg::MigrateMsg, types::TransceiverType};
+use snb_base::{
+    converters::{get_addr_by_prefix, get_ibc_hooks_sender, get_nonce, get_pfm_sender},
+    error::ContractError,
+    nft_minter::types::{
+        CollectionFlavor, Cw2981Metadata, Cw2981QueryMsg, Cw2981RoyaltiesInfoResponse, Metadata,
+        RoyaltyInfo, Sg721CollectionInfo, Sg721InstantiateMsg, TokenMetadata,
+    },
+    private_communication::types::Hash,
+    transceiver::{
+        msg::{ExecuteMsg, MigrateMsg, QueryMsg, SudoMsg},
+        state::{
+            CHANNEL_NEUTRON_STARGAZE, CHANNEL_STARGAZE_NEUTRON, DENOM_NTRN, ENC_KEY_GRACE_PERIOD,
+            IBC_RECEIVE_REPLY, IBC_TIMEOUT, LEGACY_ENC_KEY_ID, MAX_IBC_MEMO_LEN, MIN_NTRN_IBC_FEE,
+            NATIVE_VERSION, PREFIX_STARGAZE, SAVE_CONTROL_SEQUENCE_REPLY, SAVE_IBC_SEQUENCE_REPLY,
+        },
+        types::{
+            CarrierRequirement, Channel, Collection, Control, ControlAction, ControlPacket,
+            ControlStatus, EncKey, FeeBalance, FeeRefunderFee, FeeRefunderParams,
+            FeeRefunderParamsResponse, Hop, IbcAck, IbcFee, IbcLifecycleComplete, Ics721Memo,
+            NonFungibleTokenPacketData, Outpost, Packet, RequestPacket, TimeoutBounds,
+            TransceiverType, Transfer, TransferStatus, Transport,
+        },
+    },
+};
 
 use crate::helpers::{
     nft_minter::NftMinterExtension,
     suite::{
-        core::{to_string_vec, Project},
-        types::{ProjectAccount, ProjectCoin, ProjectNft},
+        codes::WithCodes,
+        core::{assert_error, to_string_vec, Project},
+        types::{ProjectAccount, ProjectCoin, ProjectNft, ENC_KEY},
     },
-    transceiver::TransceiverExtension,
+    transceiver::{mock_transceiver, TransceiverExtension, MOCK_HUB, MOCK_OUTPOST},
 };
 
 #[test]
@@ -24,17 +72,84 @@ fn migrate_default() {
             p.get_transceiver_hub_address(),
             &MigrateMsg {
                 version: "1.0.0".to_string(),
+                enc_key: None,
+            },
+            p.get_transceiver_code_id(),
+        )
+        .unwrap();
+}
+
+#[test]
+fn migrate_enc_key() -> StdResult<()> {
+    let mut p = Project::new();
+    let hub = p.get_transceiver_hub_address();
+
+    // legacy contract doesn't store encryption key
+    Item::<EncKey>::new("enc_key").remove(p.app.contract_storage_mut(&hub).as_mut());
+
+    let res = p
+        .app
+        .migrate_contract(
+            ProjectAccount::Admin.into(),
+            hub.clone(),
+            &MigrateMsg {
+                version: "1.0.0".to_string(),
+                enc_key: None,
+            },
+            p.get_transceiver_code_id(),
+        )
+        .unwrap_err()
+        .root_cause()
+        .to_string();
+    assert_that(&res).contains(
+        ContractError::ParameterIsNotFound {
+            value: "enc_key".to_string(),
+        }
+        .to_string()
+        .as_str(),
+    );
+
+    p.app
+        .migrate_contract(
+            ProjectAccount::Admin.into(),
+            hub.clone(),
+            &MigrateMsg {
+                version: "1.0.0".to_string(),
+                enc_key: Some(ENC_KEY.to_string()),
             },
             p.get_transceiver_code_id(),
         )
         .unwrap();
+
+    let enc_key: EncKey = Item::new("enc_key").load(p.app.contract_storage(&hub).as_ref())?;
+    assert_that(&enc_key).is_equal_to(EncKey {
+        id: LEGACY_ENC_KEY_ID,
+        value: ENC_KEY.to_string(),
+        expiration: None,
+    });
+
+    // in-flight packets sent before migration don't have key id, the migrated key is used
+    let msg: ExecuteMsg =
+        from_json(r#"{"accept":{"msg":"","timestamp":"0","nonce":null}}"#.as_bytes())?;
+    assert_that(&msg).is_equal_to(ExecuteMsg::Accept {
+        msg: String::default(),
+        timestamp: Timestamp::default(),
+        key_id: LEGACY_ENC_KEY_ID,
+        nonce: None,
+    });
+
+    Ok(())
 }
 
 #[test]
 fn local_transfer() -> StdResult<()> {
     let mut p = Project::new();
 
-    p.nft_minter_try_create_collection(ProjectAccount::Admin, "gopniks")?;
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
 
     let collection_list = p.nft_minter_query_collection_list(9, None)?;
     let (collection_gopniks, _) = collection_list.first().unwrap();
@@ -66,6 +181,7 @@ fn local_transfer() -> StdResult<()> {
         collection_gopniks,
         &["1", "2"],
         Some(p.get_transceiver_hub_address()),
+        None,
         1,
         ProjectCoin::Stars,
     )?;
@@ -75,6 +191,15 @@ fn local_transfer() -> StdResult<()> {
     assert_that(&alice_nft_home_after).is_equal_to(to_string_vec(&["3"]));
     assert_that(&alice_nft_hub_after).is_equal_to(to_string_vec(&["1", "2"]));
 
+    // token_uri is copied from the home collection
+    let nft_info: cw721::NftInfoResponse<Option<Empty>> = p.app.wrap().query_wasm_smart(
+        collection_gopniks,
+        &cw721::Cw721QueryMsg::NftInfo {
+            token_id: "1".to_string(),
+        },
+    )?;
+    assert_that(&nft_info.token_uri).is_equal_to(Some("https://www.Gopniks.com".to_string()));
+
     // send hub -> outpost
     p.increase_allowances_nft(
         ProjectAccount::Alice,
@@ -93,6 +218,7 @@ fn local_transfer() -> StdResult<()> {
         collection_gopniks,
         &["1", "2"],
         Some(p.get_transceiver_outpost_address()),
+        None,
         1,
         ProjectCoin::Stars,
     )?;
@@ -102,6 +228,508 @@ fn local_transfer() -> StdResult<()> {
     assert_that(&alice_nft_home_after).is_equal_to(to_string_vec(&["1", "2", "3"]));
     assert_that(&alice_nft_hub_after).is_equal_to(to_string_vec(&[]));
 
+    // local transfers are delivered in the same tx
+    let transfer = p.transceiver_query_transfer(TransceiverType::Hub, 1)?;
+    assert_that(&transfer.timestamp.seconds()).is_equal_to(p.get_block_time());
+    assert_that(&transfer).is_equal_to(Transfer {
+        id: 1,
+        sender: ProjectAccount::Alice.into(),
+        recipient: ProjectAccount::Alice.to_string(),
+        hub_collection: collection_gopniks.to_string(),
+        home_collection: ProjectNft::Gopniks.to_string(),
+        token_list: to_string_vec(&["1", "2"]),
+        metadata_list: vec![
+            TokenMetadata {
+                token_uri: Some("https://www.Gopniks.com".to_string()),
+                extension: None,
+                royalty_info: None,
+            };
+            2
+        ],
+        carrier: None,
+        ibc_fee: None,
+        fee: None,
+        timestamp: transfer.timestamp,
+        channel: None,
+        sequence: None,
+        status: TransferStatus::Delivered,
+    });
+
+    let transfer_list = p.transceiver_query_transfers_by_sender(
+        TransceiverType::Outpost,
+        ProjectAccount::Alice,
+        None,
+        None,
+    )?;
+    assert_that(&transfer_list.len()).is_equal_to(1);
+
+    let transfer_list = p.transceiver_query_transfers_by_sender(
+        TransceiverType::Outpost,
+        ProjectAccount::Bob,
+        None,
+        None,
+    )?;
+    assert_that(&transfer_list.len()).is_equal_to(0);
+
+    let transfer_list = p.transceiver_query_pending_transfers(TransceiverType::Hub, None, None)?;
+    assert_that(&transfer_list.len()).is_equal_to(0);
+
+    Ok(())
+}
+
+#[test]
+fn local_transfer_royalties() -> StdResult<()> {
+    let mut p = Project::new();
+
+    let sg721_code_id = p.store_sg721_code();
+    let cw2981_code_id = p.store_cw2981_code();
+    p.nft_minter_try_update_config(
+        ProjectAccount::Admin,
+        &None,
+        None,
+        None,
+        Some(sg721_code_id),
+        Some(cw2981_code_id),
+    )?;
+
+    // sg721 home collection has collection royalty, cw2981 one has token royalties
+    let home_pinjeons = p.instantiate_contract(
+        sg721_code_id,
+        "pinjeons",
+        &Sg721InstantiateMsg {
+            name: "pinjeons".to_string(),
+            symbol: "PINJ".to_string(),
+            minter: ProjectAccount::Owner.to_string(),
+            collection_info: Sg721CollectionInfo {
+                creator: ProjectAccount::Owner.to_string(),
+                description: String::default(),
+                image: "ipfs://pinjeons.png".to_string(),
+                external_link: None,
+                explicit_content: None,
+                start_trading_time: None,
+                royalty_info: Some(RoyaltyInfo {
+                    payment_address: ProjectAccount::Bob.to_string(),
+                    share: Decimal::permille(75),
+                }),
+            },
+        },
+    );
+    let home_badgers = p.instantiate_contract(
+        cw2981_code_id,
+        "badgers",
+        &cw721_base::msg::InstantiateMsg {
+            name: "badgers".to_string(),
+            symbol: "BADG".to_string(),
+            minter: ProjectAccount::Owner.to_string(),
+        },
+    );
+
+    p.app
+        .execute_contract(
+            ProjectAccount::Owner.into(),
+            home_pinjeons.clone(),
+            &cw721_base::ExecuteMsg::Mint::<Metadata, Empty> {
+                token_id: "1".to_string(),
+                owner: ProjectAccount::Alice.to_string(),
+                token_uri: None,
+                extension: Metadata::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    for (token_id, royalty_percentage) in [("1", Some(7)), ("2", None)] {
+        p.app
+            .execute_contract(
+                ProjectAccount::Owner.into(),
+                home_badgers.clone(),
+                &cw721_base::ExecuteMsg::Mint::<Option<Cw2981Metadata>, Empty> {
+                    token_id: token_id.to_string(),
+                    owner: ProjectAccount::Alice.to_string(),
+                    token_uri: None,
+                    extension: Some(Cw2981Metadata {
+                        metadata: Metadata::default(),
+                        royalty_percentage,
+                        royalty_payment_address: royalty_percentage
+                            .map(|_| ProjectAccount::John.to_string()),
+                    }),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    // hub collections are created from cw2981 code
+    let mut hub_collections = vec![];
+    for (name, home_collection) in [("pinjeons", &home_pinjeons), ("badgers", &home_badgers)] {
+        p.app
+            .execute_contract(
+                ProjectAccount::Admin.into(),
+                p.get_nft_minter_address(),
+                &snb_base::nft_minter::msg::ExecuteMsg::CreateCollection {
+                    name: name.to_string(),
+                    symbol: None,
+                    description: None,
+                    image: None,
+                    external_link: None,
+                    label: None,
+                    flavor: Some(CollectionFlavor::Cw2981),
+                    royalty_info: None,
+                    minter: Some(p.get_transceiver_hub_address().to_string()),
+                },
+                &[],
+            )
+            .unwrap();
+
+        let hub_collection = p
+            .nft_minter_query_collection_list(9, None)?
+            .into_iter()
+            .find(|(_, x)| x.name == name)
+            .map(|(address, _)| address)
+            .unwrap();
+
+        for transceiver in [TransceiverType::Hub, TransceiverType::Outpost] {
+            p.transceiver_try_add_collection(
+                ProjectAccount::Admin,
+                transceiver,
+                &hub_collection,
+                home_collection,
+            )?;
+        }
+
+        p.increase_allowances_nft(
+            ProjectAccount::Alice,
+            p.get_transceiver_outpost_address(),
+            home_collection,
+        );
+
+        hub_collections.push(hub_collection);
+    }
+
+    let (hub_pinjeons, hub_badgers) = (&hub_collections[0], &hub_collections[1]);
+
+    // royalty recipient is configured for the hub collections only
+    let res = p
+        .transceiver_try_update_collection(
+            ProjectAccount::Admin,
+            TransceiverType::Outpost,
+            hub_pinjeons,
+            None,
+            None,
+            None,
+            None,
+            Some(ProjectAccount::John.to_string().as_str()),
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongActionType);
+
+    p.transceiver_try_update_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        hub_pinjeons,
+        None,
+        None,
+        None,
+        None,
+        Some(ProjectAccount::John.to_string().as_str()),
+    )?;
+
+    let mut is_royalty_dropped = vec![];
+    for (hub_collection, token_list) in [(hub_pinjeons, vec!["1"]), (hub_badgers, vec!["1", "2"])] {
+        let res = p.transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            hub_collection,
+            &token_list,
+            Some(p.get_transceiver_hub_address()),
+            None,
+            1,
+            ProjectCoin::Stars,
+        )?;
+
+        is_royalty_dropped.push(res.events.iter().any(|x| {
+            x.attributes
+                .iter()
+                .any(|y| y.key == "royalty" && y.value == "dropped")
+        }));
+    }
+    assert_that(&is_royalty_dropped).is_equal_to(vec![false, false]);
+
+    // collection royalty is applied to the token and paid to the configured recipient,
+    // cw2981 share is rounded to the whole percent, token royalty is paid to the home
+    // payment address converted to the hub prefix without the configured recipient
+    for (hub_collection, token_id, address, royalty_amount) in [
+        (hub_pinjeons, "1", ProjectAccount::John.to_string(), 8),
+        (hub_badgers, "1", ProjectAccount::John.to_string(), 7),
+        (hub_badgers, "2", String::default(), 0),
+    ] {
+        let royalty_info: Cw2981RoyaltiesInfoResponse = p.app.wrap().query_wasm_smart(
+            hub_collection,
+            &cw721_base::QueryMsg::Extension {
+                msg: Cw2981QueryMsg::RoyaltyInfo {
+                    token_id: token_id.to_string(),
+                    sale_price: Uint128::new(100),
+                },
+            },
+        )?;
+
+        assert_that(&royalty_info).is_equal_to(Cw2981RoyaltiesInfoResponse {
+            address,
+            royalty_amount: Uint128::new(royalty_amount),
+        });
+    }
+
+    // token royalty is read from the home collection for the reference sale price
+    let transfer = p.transceiver_query_transfer(TransceiverType::Outpost, 2)?;
+    assert_that(&transfer.metadata_list[0].royalty_info).is_equal_to(Some(RoyaltyInfo {
+        payment_address: ProjectAccount::John.to_string(),
+        share: Decimal::percent(7),
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn collection_policy() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let (collection_gopniks, _) = collection_list.first().unwrap();
+
+    for transceiver in [TransceiverType::Hub, TransceiverType::Outpost] {
+        p.transceiver_try_add_collection(
+            ProjectAccount::Admin,
+            transceiver,
+            collection_gopniks,
+            ProjectNft::Gopniks,
+        )?;
+    }
+
+    let res = p
+        .transceiver_try_update_collection(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    let fee = coin(2, ProjectCoin::Stars.to_string());
+    p.transceiver_try_update_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Outpost,
+        collection_gopniks,
+        Some(1),
+        None,
+        Some(fee.clone()),
+        Some(2),
+        None,
+    )?;
+
+    let collection =
+        p.transceiver_query_collection(TransceiverType::Outpost, Some(collection_gopniks), None)?;
+    assert_that(&collection).is_equal_to(Collection {
+        token_limit: Some(1),
+        fee: Some(fee),
+        daily_quota: Some(2),
+        ..Collection::new(
+            collection_gopniks.as_str(),
+            &ProjectNft::Gopniks.to_string(),
+        )
+    });
+
+    p.increase_allowances_nft(
+        ProjectAccount::Alice,
+        p.get_transceiver_outpost_address(),
+        &ProjectNft::Gopniks.into(),
+    );
+
+    let res = p
+        .transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            &["1", "2"],
+            Some(p.get_transceiver_hub_address()),
+            None,
+            3,
+            ProjectCoin::Stars,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::ExceededTokenLimit);
+
+    let res = p
+        .transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            &["1"],
+            Some(p.get_transceiver_hub_address()),
+            None,
+            1,
+            ProjectCoin::Stars,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongFundsCombination);
+
+    // fee is sent to the admin
+    let admin_balance_before = p.query_balance(ProjectAccount::Admin, &ProjectCoin::Stars)?;
+
+    for token in ["1", "2"] {
+        p.transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            &[token],
+            Some(p.get_transceiver_hub_address()),
+            None,
+            3,
+            ProjectCoin::Stars,
+        )?;
+    }
+
+    let admin_balance_after = p.query_balance(ProjectAccount::Admin, &ProjectCoin::Stars)?;
+    assert_that(&(admin_balance_after - admin_balance_before)).is_equal_to(4);
+
+    let res = p
+        .transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            &["3"],
+            Some(p.get_transceiver_hub_address()),
+            None,
+            3,
+            ProjectCoin::Stars,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::ExceededDailyQuota);
+
+    // daily usage is kept on quota update
+    p.transceiver_try_update_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Outpost,
+        collection_gopniks,
+        None,
+        None,
+        None,
+        Some(2),
+        None,
+    )?;
+
+    let res = p
+        .transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            &["3"],
+            Some(p.get_transceiver_hub_address()),
+            None,
+            3,
+            ProjectCoin::Stars,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::ExceededDailyQuota);
+
+    // paused collection on the hub rejects received tokens
+    p.wait(24 * 3600);
+    p.transceiver_try_update_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_gopniks,
+        None,
+        Some(true),
+        None,
+        None,
+        None,
+    )?;
+
+    let res = p
+        .transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            &["3"],
+            Some(p.get_transceiver_hub_address()),
+            None,
+            3,
+            ProjectCoin::Stars,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionIsPaused);
+
+    p.transceiver_try_update_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_gopniks,
+        None,
+        Some(false),
+        None,
+        None,
+        None,
+    )?;
+    p.transceiver_try_update_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Outpost,
+        collection_gopniks,
+        None,
+        Some(true),
+        None,
+        None,
+        None,
+    )?;
+
+    let res = p
+        .transceiver_try_send(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            collection_gopniks,
+            &["3"],
+            Some(p.get_transceiver_hub_address()),
+            None,
+            3,
+            ProjectCoin::Stars,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionIsPaused);
+
+    // zero values reset the settings
+    p.transceiver_try_update_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Outpost,
+        collection_gopniks,
+        Some(0),
+        Some(false),
+        Some(coin(0, ProjectCoin::Stars.to_string())),
+        Some(0),
+        None,
+    )?;
+
+    p.transceiver_try_send(
+        ProjectAccount::Alice,
+        TransceiverType::Outpost,
+        collection_gopniks,
+        &["3"],
+        Some(p.get_transceiver_hub_address()),
+        None,
+        1,
+        ProjectCoin::Stars,
+    )?;
+
+    let alice_nft_hub = p.query_nft(ProjectAccount::Alice, collection_gopniks);
+    assert_that(&alice_nft_hub).is_equal_to(to_string_vec(&["1", "2", "3"]));
+
     Ok(())
 }
 
@@ -109,7 +737,11 @@ fn local_transfer() -> StdResult<()> {
 fn short_local_transfer_between_users() -> StdResult<()> {
     let mut p = Project::new();
 
-    p.nft_minter_try_create_collection(ProjectAccount::Admin, "gopniks")?;
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
 
     let collection_list = p.nft_minter_query_collection_list(9, None)?;
     let (collection_gopniks, _) = collection_list.first().unwrap();
@@ -141,6 +773,7 @@ fn short_local_transfer_between_users() -> StdResult<()> {
         collection_gopniks,
         &["1", "2"],
         Some(p.get_transceiver_hub_address()),
+        None,
         1,
         ProjectCoin::Stars,
     )?;
@@ -178,6 +811,7 @@ fn short_local_transfer_between_users() -> StdResult<()> {
         collection_gopniks,
         &["1", "2"],
         Some(p.get_transceiver_outpost_address()),
+        None,
         1,
         ProjectCoin::Stars,
     )?;
@@ -190,5 +824,2366 @@ fn short_local_transfer_between_users() -> StdResult<()> {
     Ok(())
 }
 
-// TODO: check wrong target
-// TODO: check other guards
+#[test]
+fn sudo_timeout_unknown_transfer() -> StdResult<()> {
+    let mut p = Project::new();
+
+    // neutron sends callbacks for any transfer, it must not fail
+    p.app
+        .wasm_sudo(
+            p.get_transceiver_hub_address(),
+            &SudoMsg::Timeout {
+                request: RequestPacket {
+                    sequence: Some(1),
+                    source_port: Some("transfer".to_string()),
+                    source_channel: Some("channel-18".to_string()),
+                    destination_port: Some("transfer".to_string()),
+                    destination_channel: Some("channel-191".to_string()),
+                    data: None,
+                    timeout_height: None,
+                    timeout_timestamp: None,
+                },
+            },
+        )
+        .unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn register_outpost() -> StdResult<()> {
+    let mut p = Project::new();
+    let outpost = p.get_transceiver_outpost_address();
+
+    let res = p
+        .transceiver_try_register_outpost(
+            ProjectAccount::Alice,
+            TransceiverType::Hub,
+            &outpost,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    let res = p
+        .transceiver_try_register_outpost(
+            ProjectAccount::Admin,
+            TransceiverType::Outpost,
+            &outpost,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongActionType);
+
+    let outpost_list = p.transceiver_query_outposts(TransceiverType::Hub, None, None)?;
+    assert_that(&outpost_list).is_equal_to(vec![Outpost {
+        prefix: "wasm".to_string(),
+        address: outpost.to_string(),
+        channel: None,
+    }]);
+
+    p.transceiver_try_register_outpost(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        &outpost,
+        Some("channel-0"),
+        Some("channel-1"),
+        None,
+        None,
+        None,
+        None,
+    )?;
+
+    let outpost_list = p.transceiver_query_outposts(TransceiverType::Hub, None, None)?;
+    assert_that(&outpost_list).is_equal_to(vec![Outpost {
+        prefix: "wasm".to_string(),
+        address: outpost.to_string(),
+        channel: Some(Channel::new("wasm", "channel-0", "channel-1")),
+    }]);
+
+    let outpost_list = p.transceiver_query_outposts(TransceiverType::Hub, Some("wasm"), None)?;
+    assert_that(&outpost_list).is_equal_to(vec![]);
+
+    p.transceiver_try_remove_outpost(ProjectAccount::Admin, TransceiverType::Hub, "wasm")?;
+
+    let outpost_list = p.transceiver_query_outposts(TransceiverType::Hub, None, None)?;
+    assert_that(&outpost_list).is_equal_to(vec![]);
+
+    Ok(())
+}
+
+#[test]
+fn set_and_remove_channel() -> StdResult<()> {
+    let mut p = Project::new();
+
+    // the hub uses channels of registered outposts
+    let res = p
+        .transceiver_try_set_channel(
+            ProjectAccount::Admin,
+            TransceiverType::Hub,
+            "osmo",
+            "channel-0",
+            "channel-1",
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongActionType);
+
+    let channel_list = p.transceiver_query_channel_list(TransceiverType::Hub, None, None)?;
+    assert_that(&channel_list).is_equal_to(vec![]);
+
+    let res = p
+        .transceiver_try_set_channel(
+            ProjectAccount::Alice,
+            TransceiverType::Outpost,
+            "osmo",
+            "channel-0",
+            "channel-1",
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    let res = p
+        .transceiver_try_set_channel(
+            ProjectAccount::Admin,
+            TransceiverType::Outpost,
+            "osmo",
+            "channel-0",
+            "channel1",
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongChannelId);
+
+    p.transceiver_try_set_channel(
+        ProjectAccount::Admin,
+        TransceiverType::Outpost,
+        "osmo",
+        "channel-0",
+        "channel-1",
+        None,
+        None,
+        Some("osmosis-1"),
+        None,
+    )?;
+
+    // other channels are kept
+    let channel_list = p.transceiver_query_channel_list(TransceiverType::Outpost, None, None)?;
+    assert_that(&channel_list).is_equal_to(vec![
+        Channel::new("osmo", "channel-0", "channel-1").with_chain_id(Some("osmosis-1".to_string())),
+        Channel::new(
+            PREFIX_STARGAZE,
+            CHANNEL_NEUTRON_STARGAZE,
+            CHANNEL_STARGAZE_NEUTRON,
+        ),
+    ]);
+
+    let channel_list =
+        p.transceiver_query_channel_list(TransceiverType::Outpost, Some("osmo"), None)?;
+    assert_that(&channel_list).is_equal_to(vec![Channel::new(
+        PREFIX_STARGAZE,
+        CHANNEL_NEUTRON_STARGAZE,
+        CHANNEL_STARGAZE_NEUTRON,
+    )]);
+
+    p.transceiver_try_remove_channel(ProjectAccount::Admin, TransceiverType::Outpost, "osmo")?;
+
+    let res = p
+        .transceiver_try_remove_channel(ProjectAccount::Admin, TransceiverType::Outpost, "osmo")
+        .unwrap_err();
+    assert_error(&res, ContractError::ChannelIsNotFound);
+
+    let channel_list = p.transceiver_query_channel_list(TransceiverType::Outpost, None, Some(1))?;
+    assert_that(&channel_list).is_equal_to(vec![Channel::new(
+        PREFIX_STARGAZE,
+        CHANNEL_NEUTRON_STARGAZE,
+        CHANNEL_STARGAZE_NEUTRON,
+    )]);
+
+    Ok(())
+}
+
+#[test]
+fn add_and_remove_collections() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "pinjeons",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let mut hub_collections: Vec<Addr> = collection_list.into_iter().map(|(x, _)| x).collect();
+    hub_collections.sort();
+    let (collection_a, collection_b) = (&hub_collections[0], &hub_collections[1]);
+
+    p.transceiver_try_add_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_a,
+        ProjectNft::Gopniks,
+    )?;
+
+    let res = p
+        .transceiver_try_add_collection(
+            ProjectAccount::Admin,
+            TransceiverType::Hub,
+            collection_b,
+            ProjectNft::Gopniks,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionDuplication);
+
+    let res = p
+        .transceiver_try_add_collection(
+            ProjectAccount::Admin,
+            TransceiverType::Hub,
+            collection_a,
+            ProjectNft::Pinjeons,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionDuplication);
+
+    p.transceiver_try_add_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_b,
+        ProjectNft::Pinjeons,
+    )?;
+
+    let collection = p.transceiver_query_collection(
+        TransceiverType::Hub,
+        None,
+        Some(&ProjectNft::Pinjeons.into()),
+    )?;
+    assert_that(&collection.hub_collection).is_equal_to(collection_b.to_string());
+
+    let collection_list =
+        p.transceiver_query_collection_list(TransceiverType::Hub, None, Some(1))?;
+    assert_that(&collection_list).is_equal_to(vec![Collection::new(
+        collection_a.as_str(),
+        &ProjectNft::Gopniks.to_string(),
+    )]);
+
+    let collection_list =
+        p.transceiver_query_collection_list(TransceiverType::Hub, Some(collection_a), None)?;
+    assert_that(&collection_list).is_equal_to(vec![Collection::new(
+        collection_b.as_str(),
+        &ProjectNft::Pinjeons.to_string(),
+    )]);
+
+    p.transceiver_try_remove_collection(ProjectAccount::Admin, TransceiverType::Hub, collection_b)?;
+
+    let res = p
+        .transceiver_query_collection(
+            TransceiverType::Hub,
+            None,
+            Some(&ProjectNft::Pinjeons.into()),
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionIsNotFound);
+
+    let collection_list = p.transceiver_query_collection_list(TransceiverType::Hub, None, None)?;
+    assert_that(&collection_list.len()).is_equal_to(1);
+
+    Ok(())
+}
+
+#[test]
+fn create_collection() -> StdResult<()> {
+    let mut p = Project::new();
+
+    let res = p
+        .transceiver_try_create_collection(
+            ProjectAccount::Alice,
+            TransceiverType::Hub,
+            ProjectNft::Gopniks,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    let res = p
+        .transceiver_try_create_collection(
+            ProjectAccount::Admin,
+            TransceiverType::Outpost,
+            ProjectNft::Gopniks,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongActionType);
+
+    // collection on the hub network
+    p.transceiver_try_create_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        ProjectNft::Gopniks,
+        None,
+        None,
+        None,
+    )?;
+
+    let collection = p.transceiver_query_collection(
+        TransceiverType::Hub,
+        None,
+        Some(&ProjectNft::Gopniks.into()),
+    )?;
+    let home_info: cw721::ContractInfoResponse = p.app.wrap().query_wasm_smart(
+        ProjectNft::Gopniks.to_string(),
+        &cw721::Cw721QueryMsg::ContractInfo {},
+    )?;
+    let hub_info: cw721::ContractInfoResponse = p.app.wrap().query_wasm_smart(
+        collection.hub_collection,
+        &cw721::Cw721QueryMsg::ContractInfo {},
+    )?;
+    assert_that(&hub_info).is_equal_to(home_info);
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    assert_that(&collection_list.len()).is_equal_to(1);
+
+    let res = p
+        .transceiver_try_create_collection(
+            ProjectAccount::Admin,
+            TransceiverType::Hub,
+            ProjectNft::Gopniks,
+            Some("gopniks"),
+            Some("GOP"),
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::CollectionDuplication);
+
+    // remote collection requires name and symbol
+    let home_collection = get_addr_by_prefix(ProjectNft::Pinjeons, PREFIX_STARGAZE)?;
+
+    let res = p
+        .transceiver_try_create_collection(
+            ProjectAccount::Admin,
+            TransceiverType::Hub,
+            &home_collection,
+            Some("pinjeons"),
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::NoParameters);
+
+    p.transceiver_try_create_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        &home_collection,
+        Some("pinjeons"),
+        Some("PIN"),
+        None,
+    )?;
+
+    let collection = p.transceiver_query_collection(
+        TransceiverType::Hub,
+        None,
+        Some(&Addr::unchecked(&home_collection)),
+    )?;
+    let hub_info: cw721::ContractInfoResponse = p.app.wrap().query_wasm_smart(
+        collection.hub_collection,
+        &cw721::Cw721QueryMsg::ContractInfo {},
+    )?;
+    assert_that(&hub_info).is_equal_to(cw721::ContractInfoResponse {
+        name: "pinjeons".to_string(),
+        symbol: "PIN".to_string(),
+    });
+
+    Ok(())
+}
+
+#[test]
+fn accept_unregistered_outpost() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let (collection_gopniks, _) = collection_list.first().unwrap();
+
+    p.transceiver_try_add_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_gopniks,
+        ProjectNft::Gopniks,
+    )?;
+    p.transceiver_try_remove_outpost(ProjectAccount::Admin, TransceiverType::Hub, "wasm")?;
+
+    let outpost = p.get_transceiver_outpost_address();
+    let packet = Packet {
+        id: 1,
+        sender: outpost.to_string(),
+        recipient: ProjectAccount::Alice.to_string(),
+        hub_collection: collection_gopniks.to_string(),
+        home_collection: ProjectNft::Gopniks.to_string(),
+        token_list: to_string_vec(&["1"]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(ENC_KEY)?,
+        1,
+        &get_nonce(outpost.as_str(), 1),
+        &p.app.block_info().time,
+        &packet,
+    )?;
+
+    let res = p
+        .transceiver_try_accept(outpost, TransceiverType::Hub, &encrypted_response)
+        .unwrap_err();
+    assert_error(&res, ContractError::OutpostIsNotFound);
+
+    Ok(())
+}
+
+#[test]
+fn accept_unauthorized_sender() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let (collection_gopniks, _) = collection_list.first().unwrap();
+
+    p.transceiver_try_add_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_gopniks,
+        ProjectNft::Gopniks,
+    )?;
+
+    let outpost = p.get_transceiver_outpost_address();
+    p.transceiver_try_register_outpost(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        &outpost,
+        Some("channel-0"),
+        Some("channel-1"),
+        None,
+        None,
+        None,
+        None,
+    )?;
+
+    let packet = Packet {
+        id: 1,
+        sender: outpost.to_string(),
+        recipient: ProjectAccount::Alice.to_string(),
+        hub_collection: collection_gopniks.to_string(),
+        home_collection: ProjectNft::Gopniks.to_string(),
+        token_list: to_string_vec(&["1"]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(ENC_KEY)?,
+        1,
+        &get_nonce(outpost.as_str(), 1),
+        &p.app.block_info().time,
+        &packet,
+    )?;
+
+    let res = p
+        .transceiver_try_accept(
+            ProjectAccount::Alice,
+            TransceiverType::Hub,
+            &encrypted_response,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    // ibc-hooks derives the sender from the destination channel
+    let ibc_hooks_sender = get_ibc_hooks_sender("channel-0", outpost.as_str(), "wasm")?;
+    p.transceiver_try_accept(
+        Addr::unchecked(ibc_hooks_sender),
+        TransceiverType::Hub,
+        &encrypted_response,
+    )?;
+
+    let alice_nft_hub = p.query_nft(ProjectAccount::Alice, collection_gopniks);
+    assert_that(&alice_nft_hub).is_equal_to(to_string_vec(&["1"]));
+
+    Ok(())
+}
+
+#[test]
+fn accept_multi_hop_transfer() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let (collection_gopniks, _) = collection_list.first().unwrap();
+
+    p.transceiver_try_add_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_gopniks,
+        ProjectNft::Gopniks,
+    )?;
+
+    let outpost = p.get_transceiver_outpost_address();
+    let route = vec![Hop {
+        prefix: "osmo".to_string(),
+        from_hub: "channel-5".to_string(),
+        to_hub: "channel-6".to_string(),
+    }];
+
+    let res = p
+        .transceiver_try_register_outpost(
+            ProjectAccount::Admin,
+            TransceiverType::Hub,
+            &outpost,
+            Some("channel-0"),
+            Some("channel-1"),
+            Some(Transport::Native),
+            Some(route.clone()),
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::RouteIsNotSupported);
+
+    p.transceiver_try_register_outpost(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        &outpost,
+        Some("channel-0"),
+        Some("channel-1"),
+        None,
+        Some(route),
+        None,
+        None,
+    )?;
+
+    let packet = Packet {
+        id: 1,
+        sender: outpost.to_string(),
+        recipient: ProjectAccount::Alice.to_string(),
+        hub_collection: collection_gopniks.to_string(),
+        home_collection: ProjectNft::Gopniks.to_string(),
+        token_list: to_string_vec(&["1"]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(ENC_KEY)?,
+        1,
+        &get_nonce(outpost.as_str(), 1),
+        &p.app.block_info().time,
+        &packet,
+    )?;
+
+    // the transfer is forwarded by the intermediate chain
+    let ibc_hooks_sender = get_ibc_hooks_sender("channel-0", outpost.as_str(), "wasm")?;
+    let res = p
+        .transceiver_try_accept(
+            Addr::unchecked(ibc_hooks_sender),
+            TransceiverType::Hub,
+            &encrypted_response,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    let pfm_sender = get_pfm_sender("channel-5", outpost.as_str(), "osmo")?;
+    let ibc_hooks_sender = get_ibc_hooks_sender("channel-0", &pfm_sender, "wasm")?;
+    p.transceiver_try_accept(
+        Addr::unchecked(ibc_hooks_sender),
+        TransceiverType::Hub,
+        &encrypted_response,
+    )?;
+
+    let alice_nft_hub = p.query_nft(ProjectAccount::Alice, collection_gopniks);
+    assert_that(&alice_nft_hub).is_equal_to(to_string_vec(&["1"]));
+
+    Ok(())
+}
+
+#[test]
+fn multi_hop_transfer_memo() -> StdResult<()> {
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(ENC_KEY)?,
+        1,
+        &get_nonce("neutron1transceiver", 1),
+        &Timestamp::default(),
+        &"",
+    )?;
+    let msg = to_json_string(&get_accept_msg(&encrypted_response))?;
+    let timeout = 300u64 * 1_000_000_000;
+
+    let memo = get_ibc_transfer_memo(
+        "neutron1hub",
+        &encrypted_response,
+        Some(&Addr::unchecked("stars1outpost")),
+        &to_string_vec(&["channel-2", "channel-3"]),
+        300,
+    )?;
+    assert_that(&memo).is_equal_to(format!(
+        r#"{{"forward":{{"receiver":"pfm","port":"transfer","channel":"channel-2","retries":2,"timeout":{timeout},"next":{{"forward":{{"receiver":"neutron1hub","port":"transfer","channel":"channel-3","retries":2,"timeout":{timeout},"next":{{"wasm":{{"contract":"neutron1hub","msg":{msg}}}}}}}}}}},"ibc_callback":"stars1outpost"}}"#
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn accept_replayed_packet() -> StdResult<()> {
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let (collection_gopniks, _) = collection_list.first().unwrap();
+
+    p.transceiver_try_add_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_gopniks,
+        ProjectNft::Gopniks,
+    )?;
+
+    let outpost = p.get_transceiver_outpost_address();
+    let packet = Packet {
+        id: 1,
+        sender: outpost.to_string(),
+        recipient: ProjectAccount::Alice.to_string(),
+        hub_collection: collection_gopniks.to_string(),
+        home_collection: ProjectNft::Gopniks.to_string(),
+        token_list: to_string_vec(&["1"]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(ENC_KEY)?,
+        1,
+        &get_nonce(outpost.as_str(), 1),
+        &p.app.block_info().time,
+        &packet,
+    )?;
+
+    p.transceiver_try_accept(outpost.clone(), TransceiverType::Hub, &encrypted_response)?;
+
+    let alice_nft_hub = p.query_nft(ProjectAccount::Alice, collection_gopniks);
+    let is_processed = p.transceiver_query_packet_state(TransceiverType::Hub, &outpost, 1)?;
+    assert_that(&alice_nft_hub).is_equal_to(to_string_vec(&["1"]));
+    assert_that(&is_processed).is_equal_to(true);
+
+    let res = p
+        .transceiver_try_accept(outpost, TransceiverType::Hub, &encrypted_response)
+        .unwrap_err();
+    assert_error(&res, ContractError::PacketDuplication);
+
+    Ok(())
+}
+
+#[test]
+fn apply_control_packets() -> StdResult<()> {
+    let mut p = Project::new();
+
+    let hub = p.get_transceiver_hub_address();
+    let mut nonce_counter = 0;
+    let mut encrypt = |p: &Project, sender: &Addr, id: u64, action: ControlAction| {
+        nonce_counter += 1;
+
+        serialize_encrypt(
+            &Hash::parse(ENC_KEY).unwrap(),
+            1,
+            &get_nonce(sender.as_str(), nonce_counter),
+            &p.app.block_info().time,
+            &ControlPacket {
+                id,
+                sender: sender.to_string(),
+                action,
+            },
+        )
+    };
+
+    // outpost must be configured to trust the hub
+    let encrypted_response = encrypt(&p, &hub, 1, ControlAction::Pause {})?;
+    let res = p
+        .transceiver_try_control(hub.clone(), TransceiverType::Outpost, &encrypted_response)
+        .unwrap_err();
+    assert_error(&res, ContractError::HubControlIsDisabled);
+
+    let res = p
+        .transceiver_try_update_config(
+            ProjectAccount::Admin,
+            TransceiverType::Hub,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+        )
+        .unwrap_err();
+    assert_error(&res, ContractError::WrongActionType);
+
+    p.transceiver_try_update_config(
+        ProjectAccount::Admin,
+        TransceiverType::Outpost,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(true),
+        None,
+    )?;
+
+    // only the hub can send control packets
+    let alice: Addr = ProjectAccount::Alice.into();
+    let alice_response = encrypt(&p, &alice, 1, ControlAction::Pause {})?;
+    let res = p
+        .transceiver_try_control(alice, TransceiverType::Outpost, &alice_response)
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    p.transceiver_try_control(hub.clone(), TransceiverType::Outpost, &encrypted_response)?;
+    let is_paused = p.transceiver_query_pause_state(TransceiverType::Outpost)?;
+    assert_that(&is_paused).is_equal_to(true);
+
+    let res = p
+        .transceiver_try_control(hub.clone(), TransceiverType::Outpost, &encrypted_response)
+        .unwrap_err();
+    assert_error(&res, ContractError::PacketDuplication);
+
+    for (id, action) in [
+        (2, ControlAction::Unpause {}),
+        (
+            3,
+            ControlAction::AddCollection {
+                hub_collection: "neutron1collection".to_string(),
+                home_collection: ProjectNft::Gopniks.to_string(),
+            },
+        ),
+        (
+            4,
+            ControlAction::UpdateConfig {
+                nft_minter: None,
+                hub_address: None,
+                token_limit: Some(5),
+                min_ntrn_ibc_fee: None,
+            },
+        ),
+    ] {
+        let encrypted_response = encrypt(&p, &hub, id, action)?;
+        p.transceiver_try_control(hub.clone(), TransceiverType::Outpost, &encrypted_response)?;
+    }
+
+    let is_paused = p.transceiver_query_pause_state(TransceiverType::Outpost)?;
+    let collection = p.transceiver_query_collection(
+        TransceiverType::Outpost,
+        None,
+        Some(&ProjectNft::Gopniks.into()),
+    )?;
+    let config = p.transceiver_query_config(TransceiverType::Outpost)?;
+    assert_that(&is_paused).is_equal_to(false);
+    assert_that(&collection.hub_collection).is_equal_to("neutron1collection".to_string());
+    assert_that(&config.token_limit).is_equal_to(5);
+
+    // failed action is returned to the hub as error
+    let encrypted_response = encrypt(
+        &p,
+        &hub,
+        5,
+        ControlAction::UpdateConfig {
+            nft_minter: None,
+            hub_address: None,
+            token_limit: None,
+            min_ntrn_ibc_fee: None,
+        },
+    )?;
+    let res = p
+        .transceiver_try_control(hub.clone(), TransceiverType::Outpost, &encrypted_response)
+        .unwrap_err();
+    assert_error(&res, ContractError::NoParameters);
+
+    let encrypted_response = encrypt(
+        &p,
+        &hub,
+        6,
+        ControlAction::RemoveCollection {
+            hub_collection: "neutron1collection".to_string(),
+        },
+    )?;
+    p.transceiver_try_control(hub, TransceiverType::Outpost, &encrypted_response)?;
+
+    let collection_list =
+        p.transceiver_query_collection_list(TransceiverType::Outpost, None, None)?;
+    assert_that(&collection_list.len()).is_equal_to(0);
+
+    Ok(())
+}
+
+#[test]
+fn send_control_packet() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let fee = Uint128::new(MIN_NTRN_IBC_FEE);
+    let funds = coins(2 * fee.u128() + 1, DENOM_NTRN);
+    let (env, admin) = mock_transceiver(&mut deps, TransceiverType::Hub, None, None, None);
+
+    let send_control = |deps: DepsMut, sender: &Addr, funds: &[Coin]| {
+        contract::execute(
+            deps,
+            env.clone(),
+            mock_info(sender.as_str(), funds),
+            ExecuteMsg::SendControl {
+                prefix: "stars".to_string(),
+                action: ControlAction::Pause {},
+            },
+        )
+    };
+    let save_sequence = |deps: DepsMut, sequence: u8| {
+        contract::reply(
+            deps,
+            env.clone(),
+            Reply {
+                id: SAVE_CONTROL_SEQUENCE_REPLY,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(Binary(vec![8, sequence])),
+                }),
+            },
+        )
+    };
+    let get_request = |sequence: u64| RequestPacket {
+        sequence: Some(sequence),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some("channel-0".to_string()),
+        destination_port: Some("transfer".to_string()),
+        destination_channel: Some("channel-1".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+
+    let alice = deps.api.addr_make("alice");
+    let err = send_control(deps.as_mut(), &alice, &funds).unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::Unauthorized);
+
+    let err = send_control(deps.as_mut(), &admin, &coins(1, DENOM_NTRN)).unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::WrongFundsCombination);
+
+    let err = send_control(deps.as_mut(), &admin, &coins(fee.u128() + 1, DENOM_NTRN)).unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::WrongFundsCombination);
+
+    // applied control packet
+    let res = send_control(deps.as_mut(), &admin, &funds).unwrap();
+    assert_that(&res.messages.len()).is_equal_to(1);
+    assert_that(&res.messages[0].id).is_equal_to(SAVE_CONTROL_SEQUENCE_REPLY);
+
+    save_sequence(deps.as_mut(), 1).unwrap();
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Response {
+            request: get_request(1),
+            data: Binary::default(),
+        },
+    )
+    .unwrap();
+    // timeout fee is returned to the admin
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::new(BankMsg::Send {
+        to_address: admin.to_string(),
+        amount: coins(fee.u128(), DENOM_NTRN),
+    })]);
+
+    let control: Control = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Control { id: 1 },
+    )?)?;
+    assert_that(&control).is_equal_to(Control {
+        id: 1,
+        prefix: "stars".to_string(),
+        action: ControlAction::Pause {},
+        carrier: coin(1, DENOM_NTRN),
+        ibc_fee: Some(IbcFee {
+            ack_fee: fee,
+            timeout_fee: fee,
+        }),
+        channel: "channel-0".to_string(),
+        sequence: Some(1),
+        status: ControlStatus::Applied,
+    });
+
+    // carrier coin and ack fee are returned to the admin on failure
+    send_control(deps.as_mut(), &admin, &funds).unwrap();
+    save_sequence(deps.as_mut(), 2).unwrap();
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Timeout {
+            request: get_request(2),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(vec![
+        SubMsg::new(BankMsg::Send {
+            to_address: admin.to_string(),
+            amount: coins(1, DENOM_NTRN),
+        }),
+        SubMsg::new(BankMsg::Send {
+            to_address: admin.to_string(),
+            amount: coins(fee.u128(), DENOM_NTRN),
+        }),
+    ]);
+
+    let control_list: Vec<Control> = from_json(contract::query(
+        deps.as_ref(),
+        env,
+        QueryMsg::ControlList {
+            start_after: Some(1),
+            limit: None,
+        },
+    )?)?;
+    assert_that(&control_list.len()).is_equal_to(1);
+    assert_that(&control_list[0].status).is_equal_to(ControlStatus::Failed);
+
+    Ok(())
+}
+
+#[test]
+fn accept_with_rotated_key() -> StdResult<()> {
+    const NEW_ENC_KEY: &str = "wwwwwwwwwwwwwwwwwwwwwwwwwwwwwwww";
+
+    let mut p = Project::new();
+
+    p.nft_minter_try_create_collection(
+        ProjectAccount::Admin,
+        "gopniks",
+        p.get_transceiver_hub_address(),
+    )?;
+
+    let collection_list = p.nft_minter_query_collection_list(9, None)?;
+    let (collection_gopniks, _) = collection_list.first().unwrap();
+
+    p.transceiver_try_add_collection(
+        ProjectAccount::Admin,
+        TransceiverType::Hub,
+        collection_gopniks,
+        ProjectNft::Gopniks,
+    )?;
+
+    let res = p
+        .transceiver_try_rotate_key(ProjectAccount::Alice, TransceiverType::Hub, NEW_ENC_KEY)
+        .unwrap_err();
+    assert_error(&res, ContractError::Unauthorized);
+
+    p.transceiver_try_rotate_key(ProjectAccount::Admin, TransceiverType::Hub, NEW_ENC_KEY)?;
+
+    let outpost = p.get_transceiver_outpost_address();
+    let get_packet = |id: u64, token_id: &str| Packet {
+        id,
+        sender: outpost.to_string(),
+        recipient: ProjectAccount::Alice.to_string(),
+        hub_collection: collection_gopniks.to_string(),
+        home_collection: ProjectNft::Gopniks.to_string(),
+        token_list: to_string_vec(&[token_id]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+
+    // previous key is accepted during grace period
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(ENC_KEY)?,
+        1,
+        &get_nonce(outpost.as_str(), 1),
+        &p.app.block_info().time,
+        &get_packet(1, "1"),
+    )?;
+    p.transceiver_try_accept(outpost.clone(), TransceiverType::Hub, &encrypted_response)?;
+
+    p.wait(ENC_KEY_GRACE_PERIOD);
+
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(ENC_KEY)?,
+        1,
+        &get_nonce(outpost.as_str(), 2),
+        &p.app.block_info().time,
+        &get_packet(2, "2"),
+    )?;
+    let res = p
+        .transceiver_try_accept(outpost.clone(), TransceiverType::Hub, &encrypted_response)
+        .unwrap_err();
+    assert_error(&res, ContractError::EncKeyIsExpired);
+
+    let encrypted_response = serialize_encrypt(
+        &Hash::parse(NEW_ENC_KEY)?,
+        2,
+        &get_nonce(outpost.as_str(), 3),
+        &p.app.block_info().time,
+        &get_packet(2, "2"),
+    )?;
+    p.transceiver_try_accept(outpost, TransceiverType::Hub, &encrypted_response)?;
+
+    let alice_nft_hub = p.query_nft(ProjectAccount::Alice, collection_gopniks);
+    assert_that(&alice_nft_hub).is_equal_to(to_string_vec(&["1", "2"]));
+
+    Ok(())
+}
+
+// TODO: check wrong target
+// TODO: check other guards
+
+#[test]
+fn ics721_receive() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let nft_minter = deps.api.addr_make("nft_minter");
+    let hub_collection = deps.api.addr_make("hub_collection");
+    let recipient = deps.api.addr_make("alice");
+    let home_collection = "stars1collection";
+    let (env, admin) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Hub,
+        Some(Transport::Ics721),
+        None,
+        Some((hub_collection.as_str(), home_collection)),
+    );
+
+    let packet_data = NonFungibleTokenPacketData {
+        class_id: home_collection.to_string(),
+        class_uri: None,
+        class_data: None,
+        token_ids: to_string_vec(&["1", "2"]),
+        token_uris: None,
+        token_data: None,
+        sender: "stars1alice".to_string(),
+        receiver: recipient.to_string(),
+        memo: None,
+    };
+
+    // packet on unknown channel is rejected with error ack
+    let res = contract::ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_recv("channel-2", &packet_data)?,
+    )
+    .unwrap();
+    assert_that(&res.messages.len()).is_equal_to(0);
+    let ack: IbcAck = from_json(res.acknowledgement)?;
+    let error = ContractError::ChannelIsNotFound.to_string();
+    assert_that(&matches!(ack, IbcAck::Error(x) if x.contains(&error))).is_equal_to(true);
+
+    // nfts are minted on the hub
+    let res = contract::ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_recv("channel-0", &packet_data)?,
+    )
+    .unwrap();
+    assert_that(&from_json::<IbcAck>(res.acknowledgement)?)
+        .is_equal_to(IbcAck::Result(Binary(vec![1])));
+    let receive_msg = ExecuteMsg::ReceiveNfts {
+        hub_collection: hub_collection.to_string(),
+        token_list: to_string_vec(&["1", "2"]),
+        metadata_list: vec![],
+        recipient: recipient.to_string(),
+        processed_packet: None,
+    };
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&receive_msg)?,
+            funds: vec![],
+        },
+        IBC_RECEIVE_REPLY,
+    )]);
+
+    // received nfts can be minted only by the contract itself
+    let err = contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(recipient.as_str(), &[]),
+        receive_msg.clone(),
+    )
+    .unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::Unauthorized);
+
+    let res = contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(env.contract.address.as_str(), &[]),
+        receive_msg,
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: nft_minter.to_string(),
+        msg: to_json_binary(&snb_base::nft_minter::msg::ExecuteMsg::Mint {
+            collection: hub_collection.to_string(),
+            token_list: to_string_vec(&["1", "2"]),
+            recipient: recipient.to_string(),
+            metadata_list: Some(vec![]),
+        })?,
+        funds: vec![],
+    })]);
+
+    // token uris and json encoded extensions are minted with the nfts
+    let metadata = Metadata {
+        name: Some("Gopnik #3".to_string()),
+        image: Some("ipfs://gopnik/3.png".to_string()),
+        ..Metadata::default()
+    };
+    let packet_data = NonFungibleTokenPacketData {
+        token_ids: to_string_vec(&["3", "4"]),
+        token_uris: Some(vec!["ipfs://gopnik/3.json".to_string(), "".to_string()]),
+        token_data: Some(vec![to_json_binary(&metadata)?, Binary::default()]),
+        ..packet_data
+    };
+    let res = contract::ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_recv("channel-0", &packet_data)?,
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::ReceiveNfts {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&["3", "4"]),
+                metadata_list: vec![
+                    TokenMetadata {
+                        token_uri: Some("ipfs://gopnik/3.json".to_string()),
+                        extension: Some(metadata),
+                        royalty_info: None,
+                    },
+                    TokenMetadata::default(),
+                ],
+                recipient: recipient.to_string(),
+                processed_packet: None,
+            })?,
+            funds: vec![],
+        },
+        IBC_RECEIVE_REPLY,
+    )]);
+
+    // packets are parsed according to the negotiated channel version, not registered transport
+    contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(admin.as_str(), &[]),
+        ExecuteMsg::RegisterOutpost {
+            address: MOCK_OUTPOST.to_string(),
+            from_hub: Some("channel-0".to_string()),
+            to_hub: Some("channel-1".to_string()),
+            transport: Some(Transport::Native),
+            route: None,
+            chain_id: None,
+            timeout: None,
+        },
+    )
+    .unwrap();
+
+    let res = contract::ibc_packet_receive(
+        deps.as_mut(),
+        env,
+        mock_ibc_packet_recv("channel-0", &packet_data)?,
+    )
+    .unwrap();
+    assert_that(&from_json::<IbcAck>(res.acknowledgement)?)
+        .is_equal_to(IbcAck::Result(Binary(vec![1])));
+
+    Ok(())
+}
+
+#[test]
+fn ics721_ack() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_collection = "neutron1collection";
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&cw721::TokensResponse {
+                tokens: to_string_vec(&["1", "2"]),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let (env, _) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        Some(Transport::Ics721),
+        None,
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    let res = contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(alice.as_str(), &[]),
+        ExecuteMsg::Send {
+            hub_collection: hub_collection.to_string(),
+            token_list: to_string_vec(&["1"]),
+            target: None,
+            timeout: None,
+        },
+    )
+    .unwrap();
+
+    // transfer id is sent in the memo
+    let packet_data: NonFungibleTokenPacketData = match &res.messages.last().unwrap().msg {
+        CosmosMsg::Ibc(IbcMsg::SendPacket { data, .. }) => from_json(data)?,
+        _ => panic!("ibc packet isn't sent"),
+    };
+    assert_that(&packet_data.memo)
+        .is_equal_to(Some(to_json_string(&Ics721Memo { transfer_id: 1 })?));
+
+    // the same tokens sent by another transfer don't affect the ack
+    let unknown_packet_data = NonFungibleTokenPacketData {
+        memo: None,
+        ..packet_data.clone()
+    };
+    let res = contract::ibc_packet_ack(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_ack(
+            "channel-1",
+            &unknown_packet_data,
+            IbcAcknowledgement::encode_json(&IbcAck::Result(Binary(vec![1])))?,
+        )?,
+    )
+    .unwrap();
+    assert_that(
+        &res.attributes
+            .iter()
+            .any(|x| x.key == "transfer" && x.value == "unknown"),
+    )
+    .is_equal_to(true);
+
+    let res = contract::ibc_packet_ack(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_ack(
+            "channel-1",
+            &packet_data,
+            IbcAcknowledgement::encode_json(&IbcAck::Result(Binary(vec![1])))?,
+        )?,
+    )
+    .unwrap();
+    assert_that(&res.messages.len()).is_equal_to(0);
+
+    let transfer: Transfer = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Transfer { id: 1 },
+    )?)?;
+    assert_that(&transfer.status).is_equal_to(TransferStatus::Delivered);
+
+    // delivered transfer isn't refunded on late timeout
+    let res = contract::ibc_packet_timeout(
+        deps.as_mut(),
+        env,
+        mock_ibc_packet_timeout("channel-1", &packet_data)?,
+    )
+    .unwrap();
+    assert_that(&res.messages.len()).is_equal_to(0);
+
+    Ok(())
+}
+
+#[test]
+fn native_transfer() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_address = MOCK_HUB;
+    let hub_collection = "neutron1collection";
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&cw721::TokensResponse {
+                tokens: to_string_vec(&["1", "2"]),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let (env, _) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        Some(Transport::Native),
+        None,
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    // carrier coin isn't required
+    let res = contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(alice.as_str(), &[]),
+        ExecuteMsg::Send {
+            hub_collection: hub_collection.to_string(),
+            token_list: to_string_vec(&["1"]),
+            target: None,
+            timeout: None,
+        },
+    )
+    .unwrap();
+
+    let packet = Packet {
+        id: 1,
+        sender: env.contract.address.to_string(),
+        recipient: get_addr_by_prefix(&alice, "neutron")?,
+        hub_collection: hub_collection.to_string(),
+        home_collection: home_collection.to_string(),
+        token_list: to_string_vec(&["1"]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+    assert_that(&res.messages.last().unwrap().msg).is_equal_to(CosmosMsg::Ibc(
+        IbcMsg::SendPacket {
+            channel_id: "channel-1".to_string(),
+            data: to_json_binary(&packet)?,
+            timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(IBC_TIMEOUT)),
+        },
+    ));
+
+    // nfts are unlocked on timeout
+    let res = contract::ibc_packet_timeout(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_timeout("channel-1", &packet)?,
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: home_collection.to_string(),
+        msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+            recipient: alice.to_string(),
+            token_id: "1".to_string(),
+        })?,
+        funds: vec![],
+    })]);
+
+    let transfer: Transfer = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Transfer { id: 1 },
+    )?)?;
+    assert_that(&transfer.status).is_equal_to(TransferStatus::Refunded);
+
+    // packet from unknown port is rejected
+    let packet = Packet {
+        id: 1,
+        sender: hub_address.to_string(),
+        recipient: alice.to_string(),
+        hub_collection: hub_collection.to_string(),
+        home_collection: home_collection.to_string(),
+        token_list: to_string_vec(&["2"]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+    let mut msg = mock_ibc_packet_recv("channel-1", &packet)?;
+    let res = contract::ibc_packet_receive(deps.as_mut(), env.clone(), msg.clone()).unwrap();
+    let ack: IbcAck = from_json(res.acknowledgement)?;
+    let error = ContractError::Unauthorized.to_string();
+    assert_that(&matches!(ack, IbcAck::Error(x) if x.contains(&error))).is_equal_to(true);
+
+    // nfts are unlocked for the packet from the hub
+    msg.packet.src.port_id = format!("wasm.{}", hub_address);
+    let res = contract::ibc_packet_receive(deps.as_mut(), env.clone(), msg.clone()).unwrap();
+    assert_that(&from_json::<IbcAck>(res.acknowledgement)?)
+        .is_equal_to(IbcAck::Result(Binary(vec![1])));
+    let receive_msg = ExecuteMsg::ReceiveNfts {
+        hub_collection: hub_collection.to_string(),
+        token_list: to_string_vec(&["2"]),
+        metadata_list: vec![],
+        recipient: alice.to_string(),
+        processed_packet: Some((hub_address.to_string(), 1)),
+    };
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&receive_msg)?,
+            funds: vec![],
+        },
+        IBC_RECEIVE_REPLY,
+    )]);
+
+    // the packet isn't recorded as processed until the nfts are received
+    let res = contract::ibc_packet_receive(deps.as_mut(), env.clone(), msg.clone()).unwrap();
+    assert_that(&from_json::<IbcAck>(res.acknowledgement)?)
+        .is_equal_to(IbcAck::Result(Binary(vec![1])));
+
+    let res = contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(env.contract.address.as_str(), &[]),
+        receive_msg,
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: home_collection.to_string(),
+        msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+            recipient: alice.to_string(),
+            token_id: "2".to_string(),
+        })?,
+        funds: vec![],
+    })]);
+
+    // the same packet can't be processed twice
+    let res = contract::ibc_packet_receive(deps.as_mut(), env, msg).unwrap();
+    let ack: IbcAck = from_json(res.acknowledgement)?;
+    let error = ContractError::PacketDuplication.to_string();
+    assert_that(&matches!(ack, IbcAck::Error(x) if x.contains(&error))).is_equal_to(true);
+
+    Ok(())
+}
+
+#[test]
+fn ibc_channel_close() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_collection = "neutron1collection";
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&cw721::TokensResponse {
+                tokens: to_string_vec(&["1", "2"]),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let (env, _) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        Some(Transport::Native),
+        None,
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    for token_id in ["1", "2"] {
+        contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(alice.as_str(), &[]),
+            ExecuteMsg::Send {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&[token_id]),
+                target: None,
+                timeout: None,
+            },
+        )
+        .unwrap();
+    }
+
+    // pending transfers may be already received by the counterparty, they aren't refunded
+    // when the channel is closed
+    let res = contract::ibc_channel_close(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_close_confirm("channel-1", IbcOrder::Unordered, NATIVE_VERSION),
+    )
+    .unwrap();
+    assert_that(&res.messages.len()).is_equal_to(0);
+
+    let transfer_list: Vec<Transfer> = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::PendingTransfers {
+            start_after: None,
+            limit: None,
+        },
+    )?)?;
+    assert_that(&transfer_list.len()).is_equal_to(2);
+
+    // not received packet is refunded on timeout on close
+    let packet = Packet {
+        id: 1,
+        sender: env.contract.address.to_string(),
+        recipient: get_addr_by_prefix(&alice, "neutron")?,
+        hub_collection: hub_collection.to_string(),
+        home_collection: home_collection.to_string(),
+        token_list: to_string_vec(&["1"]),
+        metadata_list: vec![],
+        royalty_info: None,
+    };
+    let res = contract::ibc_packet_timeout(
+        deps.as_mut(),
+        env,
+        mock_ibc_packet_timeout("channel-1", &packet)?,
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::new(WasmMsg::Execute {
+        contract_addr: home_collection.to_string(),
+        msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+            recipient: alice.to_string(),
+            token_id: "1".to_string(),
+        })?,
+        funds: vec![],
+    })]);
+
+    Ok(())
+}
+
+#[test]
+fn ibc_timeout() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_collection = "neutron1collection";
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&cw721::TokensResponse {
+                tokens: to_string_vec(&["1", "2", "3"]),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let (env, admin) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        Some(Transport::Native),
+        Some(3_600),
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    let update_timeout_bounds = |deps: DepsMut, min: u64, max: u64| {
+        contract::execute(
+            deps,
+            env.clone(),
+            mock_info(admin.as_str(), &[]),
+            ExecuteMsg::UpdateConfig {
+                admin: None,
+                nft_minter: None,
+                hub_address: None,
+                token_limit: None,
+                min_ntrn_ibc_fee: None,
+                hub_control: None,
+                timeout_bounds: Some(TimeoutBounds { min, max }),
+            },
+        )
+    };
+
+    let send = |deps: DepsMut, token_id: &str, timeout: Option<u64>| {
+        let res = contract::execute(
+            deps,
+            env.clone(),
+            mock_info(alice.as_str(), &[]),
+            ExecuteMsg::Send {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&[token_id]),
+                target: None,
+                timeout,
+            },
+        )
+        .unwrap();
+
+        let timeout = match &res.messages.last().unwrap().msg {
+            CosmosMsg::Ibc(IbcMsg::SendPacket { timeout, .. }) => timeout.timestamp().unwrap(),
+            _ => panic!("ibc packet isn't sent"),
+        };
+        let attribute = res.attributes.iter().find(|x| x.key == "timeout").unwrap();
+        assert_that(&attribute.value).is_equal_to(timeout.nanos().to_string());
+
+        timeout
+    };
+
+    // channel timeout is used by default
+    assert_that(&send(deps.as_mut(), "1", None)).is_equal_to(env.block.time.plus_seconds(3_600));
+
+    // bounds must be consistent
+    let err = update_timeout_bounds(deps.as_mut(), 0, 1_800).unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::WrongTimeoutBounds);
+    let err = update_timeout_bounds(deps.as_mut(), 1_800, 120).unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::WrongTimeoutBounds);
+
+    // specified timeout is clamped to the bounds
+    update_timeout_bounds(deps.as_mut(), 120, 1_800).unwrap();
+    assert_that(&send(deps.as_mut(), "2", Some(10))).is_equal_to(env.block.time.plus_seconds(120));
+    assert_that(&send(deps.as_mut(), "3", Some(100_000)))
+        .is_equal_to(env.block.time.plus_seconds(1_800));
+
+    Ok(())
+}
+
+#[test]
+fn send_oversized_memo() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_collection = "neutron1collection";
+
+    deps.querier.update_wasm(|query| {
+        let msg = match query {
+            WasmQuery::Smart { msg, .. } => from_json(msg),
+            _ => unimplemented!(),
+        };
+
+        SystemResult::Ok(match msg {
+            Ok(cw721::Cw721QueryMsg::NftInfo { .. }) => ContractResult::Ok(
+                to_json_binary(&cw721::NftInfoResponse::<Option<Empty>> {
+                    token_uri: Some("a".repeat(MAX_IBC_MEMO_LEN)),
+                    extension: None,
+                })
+                .unwrap(),
+            ),
+            Ok(_) => ContractResult::Ok(
+                to_json_binary(&cw721::TokensResponse {
+                    tokens: to_string_vec(&["1"]),
+                })
+                .unwrap(),
+            ),
+            Err(e) => ContractResult::Err(e.to_string()),
+        })
+    });
+
+    let (env, _) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        None,
+        None,
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    let err = contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(alice.as_str(), &coins(1, "ustars")),
+        ExecuteMsg::Send {
+            hub_collection: hub_collection.to_string(),
+            token_list: to_string_vec(&["1"]),
+            target: None,
+            timeout: None,
+        },
+    )
+    .unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::ExceededMemoSize {
+        max: MAX_IBC_MEMO_LEN,
+    });
+
+    Ok(())
+}
+
+#[test]
+fn carrier_denoms() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_collection = "neutron1collection";
+    let denom_stars = "ustars";
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&cw721::TokensResponse {
+                tokens: to_string_vec(&["1", "2"]),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let (env, admin) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        None,
+        None,
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    let set_carrier_denoms = |deps: DepsMut, sender: &Addr, prefix: &str| {
+        contract::execute(
+            deps,
+            env.clone(),
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::SetCarrierDenoms {
+                prefix: prefix.to_string(),
+                denoms: to_string_vec(&[denom_stars, denom_stars]),
+            },
+        )
+    };
+    let send = |deps: DepsMut, token_id: &str, funds: &[Coin]| {
+        contract::execute(
+            deps,
+            env.clone(),
+            mock_info(alice.as_str(), funds),
+            ExecuteMsg::Send {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&[token_id]),
+                target: None,
+                timeout: None,
+            },
+        )
+    };
+
+    let err = set_carrier_denoms(deps.as_mut(), &alice, "cosmwasm").unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::Unauthorized);
+
+    let err = set_carrier_denoms(deps.as_mut(), &admin, "osmo").unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::ChannelIsNotFound);
+
+    set_carrier_denoms(deps.as_mut(), &admin, "cosmwasm").unwrap();
+
+    let carrier: CarrierRequirement = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::CarrierRequirement {
+            hub_collection: hub_collection.to_string(),
+        },
+    )?)?;
+    assert_that(&carrier).is_equal_to(CarrierRequirement {
+        denoms: to_string_vec(&[denom_stars]),
+        amount: Uint128::one(),
+    });
+
+    // carrier denoms are kept on channel update
+    contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(admin.as_str(), &[]),
+        ExecuteMsg::SetChannel {
+            prefix: "cosmwasm".to_string(),
+            from_hub: "channel-0".to_string(),
+            to_hub: "channel-2".to_string(),
+            transport: None,
+            route: None,
+            chain_id: None,
+            timeout: None,
+        },
+    )
+    .unwrap();
+
+    let channel_list: Vec<Channel> = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::ChannelList {
+            start_after: None,
+            limit: None,
+        },
+    )?)?;
+    assert_that(&channel_list[0].carrier_denoms).is_equal_to(to_string_vec(&[denom_stars]));
+
+    // not allowlisted denom is rejected
+    let err = send(deps.as_mut(), "1", &coins(1, "uatom")).unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::WrongAssetType);
+
+    // overpayment is returned to the sender
+    let res = send(deps.as_mut(), "1", &coins(3, denom_stars)).unwrap();
+    assert_that(&res.messages[0].msg).is_equal_to(CosmosMsg::Bank(BankMsg::Send {
+        to_address: alice.to_string(),
+        amount: coins(2, denom_stars),
+    }));
+
+    let transfer: Transfer = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Transfer { id: 1 },
+    )?)?;
+    assert_that(&transfer.carrier).is_equal_to(Some(coin(1, denom_stars)));
+
+    // exact amount doesn't produce change
+    let res = send(deps.as_mut(), "2", &coins(1, denom_stars)).unwrap();
+    assert_that(
+        &res.messages
+            .iter()
+            .any(|x| matches!(x.msg, CosmosMsg::Bank(_))),
+    )
+    .is_equal_to(false);
+
+    Ok(())
+}
+
+#[test]
+fn ibc_fee_refund() -> StdResult<()> {
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: FeeRefunderQuerier {
+            base: MockQuerier::new(&[]),
+            min_fee: None,
+        },
+        custom_query_type: PhantomData,
+    };
+    let admin = deps.api.addr_make("admin");
+    let alice = deps.api.addr_make("alice");
+    let hub_collection = deps.api.addr_make("hub_collection");
+    let home_collection = "stars1collection";
+    let fee = Uint128::new(MIN_NTRN_IBC_FEE);
+
+    deps.querier.base.update_wasm(|query| {
+        let msg = match query {
+            WasmQuery::Smart { msg, .. } => from_json(msg),
+            _ => unimplemented!(),
+        };
+
+        // royalty queries aren't supported by cw721-base collection
+        SystemResult::Ok(match msg {
+            Ok(cw721::Cw721QueryMsg::AllOperators { .. }) => ContractResult::Ok(
+                to_json_binary(&cw721::OperatorsResponse { operators: vec![] }).unwrap(),
+            ),
+            Ok(_) => ContractResult::Ok(
+                to_json_binary(&cw721::TokensResponse {
+                    tokens: to_string_vec(&["1", "2"]),
+                })
+                .unwrap(),
+            ),
+            Err(e) => ContractResult::Err(e.to_string()),
+        })
+    });
+
+    let (env, _) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Hub,
+        None,
+        None,
+        Some((hub_collection.as_str(), home_collection)),
+    );
+
+    let send = |deps: DepsMut, token_id: &str, amount: Uint128| {
+        contract::execute(
+            deps,
+            env.clone(),
+            mock_info(alice.as_str(), &coins(amount.u128(), DENOM_NTRN)),
+            ExecuteMsg::Send {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&[token_id]),
+                target: None,
+                timeout: None,
+            },
+        )
+    };
+    let save_sequence = |deps: DepsMut, sequence: u8| {
+        contract::reply(
+            deps,
+            env.clone(),
+            Reply {
+                id: SAVE_IBC_SEQUENCE_REPLY,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(Binary(vec![8, sequence])),
+                }),
+            },
+        )
+    };
+    let get_request = |sequence: u64| RequestPacket {
+        sequence: Some(sequence),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some("channel-0".to_string()),
+        destination_port: Some("transfer".to_string()),
+        destination_channel: Some("channel-1".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+    let query_fee_balance = |deps: Deps| -> StdResult<FeeBalance> {
+        from_json(contract::query(deps, env.clone(), QueryMsg::FeeBalance {})?)
+    };
+
+    // configured minimum is used if fee refunder params are unavailable
+    let ibc_fee: IbcFee = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::FeeEstimate {},
+    )?)?;
+    assert_that(&ibc_fee).is_equal_to(IbcFee {
+        ack_fee: fee,
+        timeout_fee: fee,
+    });
+
+    // both ack and timeout fees are paid by the sender
+    let carrier: CarrierRequirement = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::CarrierRequirement {
+            hub_collection: hub_collection.to_string(),
+        },
+    )?)?;
+    assert_that(&carrier).is_equal_to(CarrierRequirement {
+        denoms: to_string_vec(&[DENOM_NTRN]),
+        amount: Uint128::one() + fee + fee,
+    });
+
+    let err = send(deps.as_mut(), "1", Uint128::one() + fee).unwrap_err();
+    assert_that(&err).is_equal_to(ContractError::WrongFundsCombination);
+
+    send(deps.as_mut(), "1", carrier.amount).unwrap();
+    save_sequence(deps.as_mut(), 1).unwrap();
+
+    let transfer: Transfer = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Transfer { id: 1 },
+    )?)?;
+    assert_that(&transfer.ibc_fee).is_equal_to(Some(ibc_fee));
+
+    deps.querier
+        .base
+        .update_balance(&env.contract.address, coins(fee.u128(), DENOM_NTRN));
+    assert_that(&query_fee_balance(deps.as_ref())?).is_equal_to(FeeBalance {
+        escrowed: fee + fee,
+        balance: fee,
+    });
+
+    // timeout fee is returned on ack
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Response {
+            request: get_request(1),
+            data: Binary::default(),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(vec![SubMsg::new(BankMsg::Send {
+        to_address: alice.to_string(),
+        amount: coins(fee.u128(), DENOM_NTRN),
+    })]);
+    assert_that(&query_fee_balance(deps.as_ref())?.escrowed).is_equal_to(Uint128::zero());
+
+    // fee refunder params are used if they're available
+    deps.querier.min_fee = Some(FeeRefunderFee {
+        recv_fee: vec![],
+        ack_fee: coins(2_000, DENOM_NTRN),
+        timeout_fee: coins(3_000, DENOM_NTRN),
+    });
+
+    let ibc_fee: IbcFee = from_json(contract::query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::FeeEstimate {},
+    )?)?;
+    assert_that(&ibc_fee).is_equal_to(IbcFee {
+        ack_fee: Uint128::new(2_000),
+        timeout_fee: Uint128::new(3_000),
+    });
+
+    // ack fee is returned on timeout together with the carrier
+    send(deps.as_mut(), "2", Uint128::new(5_001)).unwrap();
+    save_sequence(deps.as_mut(), 2).unwrap();
+
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Timeout {
+            request: get_request(2),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages.last().unwrap().msg).is_equal_to(CosmosMsg::Bank(BankMsg::Send {
+        to_address: alice.to_string(),
+        amount: coins(2_000, DENOM_NTRN),
+    }));
+    assert_that(&res.messages[res.messages.len() - 2].msg).is_equal_to(CosmosMsg::Bank(
+        BankMsg::Send {
+            to_address: alice.to_string(),
+            amount: coins(1, DENOM_NTRN),
+        },
+    ));
+    assert_that(&query_fee_balance(deps.as_ref())?.escrowed).is_equal_to(Uint128::zero());
+
+    // collection fee and daily usage are returned on refund
+    let collection_fee = coin(5, "ustars");
+    contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(admin.as_str(), &[]),
+        ExecuteMsg::UpdateCollection {
+            hub_collection: hub_collection.to_string(),
+            token_limit: None,
+            is_paused: None,
+            fee: Some(collection_fee.clone()),
+            daily_quota: Some(1),
+            royalty_recipient: None,
+        },
+    )
+    .unwrap();
+
+    let send_with_fee = |deps: DepsMut| {
+        contract::execute(
+            deps,
+            env.clone(),
+            mock_info(
+                alice.as_str(),
+                &[coin(5_001, DENOM_NTRN), collection_fee.clone()],
+            ),
+            ExecuteMsg::Send {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&["1"]),
+                target: None,
+                timeout: None,
+            },
+        )
+    };
+
+    let res = send_with_fee(deps.as_mut()).unwrap();
+    assert_that(
+        &res.messages
+            .iter()
+            .any(|x| matches!(x.msg, CosmosMsg::Bank(_))),
+    )
+    .is_equal_to(false);
+    save_sequence(deps.as_mut(), 3).unwrap();
+
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Error {
+            request: get_request(3),
+            details: String::default(),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages).contains(SubMsg::new(BankMsg::Send {
+        to_address: alice.to_string(),
+        amount: vec![collection_fee.clone()],
+    }));
+
+    // daily usage was reverted on refund, collection fee is sent to the admin on ack
+    send_with_fee(deps.as_mut()).unwrap();
+    save_sequence(deps.as_mut(), 4).unwrap();
+
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Response {
+            request: get_request(4),
+            data: Binary::default(),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages).contains(SubMsg::new(BankMsg::Send {
+        to_address: admin.to_string(),
+        amount: vec![collection_fee],
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn outpost_sudo_refund() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_collection = "neutron1collection";
+    let carrier = coin(1, "ustars");
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&cw721::TokensResponse {
+                tokens: to_string_vec(&["1", "2"]),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let (env, _) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        None,
+        None,
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    let send = |mut deps: DepsMut, token_id: &str, sequence: u8| {
+        contract::execute(
+            deps.branch(),
+            env.clone(),
+            mock_info(alice.as_str(), &[carrier.clone()]),
+            ExecuteMsg::Send {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&[token_id]),
+                target: None,
+                timeout: None,
+            },
+        )
+        .unwrap();
+
+        contract::reply(
+            deps,
+            env.clone(),
+            Reply {
+                id: SAVE_IBC_SEQUENCE_REPLY,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(Binary(vec![8, sequence])),
+                }),
+            },
+        )
+        .unwrap();
+    };
+    let get_request = |sequence: u64| RequestPacket {
+        sequence: Some(sequence),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some("channel-1".to_string()),
+        destination_port: Some("transfer".to_string()),
+        destination_channel: Some("channel-0".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+    let get_refund_msgs = |token_id: &str| {
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: home_collection.to_string(),
+                msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                    recipient: alice.to_string(),
+                    token_id: token_id.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: vec![carrier.clone()],
+            }),
+        ]
+    };
+    let query_status = |deps: Deps, id: u64| -> StdResult<TransferStatus> {
+        let transfer: Transfer = from_json(contract::query(
+            deps,
+            env.clone(),
+            QueryMsg::Transfer { id },
+        )?)?;
+        Ok(transfer.status)
+    };
+
+    // locked nfts and carrier coin are returned to the sender on error ack
+    send(deps.as_mut(), "1", 1);
+    assert_that(&query_status(deps.as_ref(), 1)?).is_equal_to(TransferStatus::Pending);
+
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Error {
+            request: get_request(1),
+            details: String::default(),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(get_refund_msgs("1"));
+    assert_that(&query_status(deps.as_ref(), 1)?).is_equal_to(TransferStatus::Refunded);
+
+    // and on timeout
+    send(deps.as_mut(), "2", 2);
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Timeout {
+            request: get_request(2),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(get_refund_msgs("2"));
+    assert_that(&query_status(deps.as_ref(), 2)?).is_equal_to(TransferStatus::Refunded);
+
+    // the transfer can't be refunded twice
+    let res = contract::sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::Timeout {
+            request: get_request(2),
+        },
+    )
+    .unwrap();
+    assert_that(&res.messages.len()).is_equal_to(0);
+
+    Ok(())
+}
+
+#[test]
+fn outpost_ibc_hooks_refund() -> StdResult<()> {
+    let mut deps = mock_dependencies();
+    let alice = deps.api.addr_make("alice");
+    let home_collection = deps.api.addr_make("home_collection");
+    let hub_collection = "neutron1collection";
+    let carrier = coin(1, "ustars");
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&cw721::TokensResponse {
+                tokens: to_string_vec(&["1", "2", "3"]),
+            })
+            .unwrap(),
+        ))
+    });
+
+    let (env, _) = mock_transceiver(
+        &mut deps,
+        TransceiverType::Outpost,
+        None,
+        None,
+        Some((hub_collection, home_collection.as_str())),
+    );
+
+    let send = |mut deps: DepsMut, token_id: &str, sequence: u8| {
+        contract::execute(
+            deps.branch(),
+            env.clone(),
+            mock_info(alice.as_str(), &[carrier.clone()]),
+            ExecuteMsg::Send {
+                hub_collection: hub_collection.to_string(),
+                token_list: to_string_vec(&[token_id]),
+                target: None,
+                timeout: None,
+            },
+        )
+        .unwrap();
+
+        contract::reply(
+            deps,
+            env.clone(),
+            Reply {
+                id: SAVE_IBC_SEQUENCE_REPLY,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(Binary(vec![8, sequence])),
+                }),
+            },
+        )
+        .unwrap();
+    };
+    let get_refund_msgs = |token_id: &str| {
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: home_collection.to_string(),
+                msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                    recipient: alice.to_string(),
+                    token_id: token_id.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: vec![carrier.clone()],
+            }),
+        ]
+    };
+    let query_status = |deps: Deps, id: u64| -> StdResult<TransferStatus> {
+        let transfer: Transfer = from_json(contract::query(
+            deps,
+            env.clone(),
+            QueryMsg::Transfer { id },
+        )?)?;
+        Ok(transfer.status)
+    };
+
+    // locked nfts and carrier coin are returned to the sender on failed ack
+    send(deps.as_mut(), "1", 1);
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel: "channel-1".to_string(),
+            sequence: 1,
+            ack: String::default(),
+            success: false,
+        }),
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(get_refund_msgs("1"));
+    assert_that(&query_status(deps.as_ref(), 1)?).is_equal_to(TransferStatus::Refunded);
+
+    // and on timeout
+    send(deps.as_mut(), "2", 2);
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+            channel: "channel-1".to_string(),
+            sequence: 2,
+        }),
+    )
+    .unwrap();
+    assert_that(&res.messages).is_equal_to(get_refund_msgs("2"));
+    assert_that(&query_status(deps.as_ref(), 2)?).is_equal_to(TransferStatus::Refunded);
+
+    // successful ack delivers the transfer without refund
+    send(deps.as_mut(), "3", 3);
+    let res = contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel: "channel-1".to_string(),
+            sequence: 3,
+            ack: String::default(),
+            success: true,
+        }),
+    )
+    .unwrap();
+    assert_that(&res.messages.len()).is_equal_to(0);
+    assert_that(&query_status(deps.as_ref(), 3)?).is_equal_to(TransferStatus::Delivered);
+
+    Ok(())
+}
+
+/// mock querier responding to neutron fee refunder params query
+struct FeeRefunderQuerier {
+    base: MockQuerier,
+    min_fee: Option<FeeRefunderFee>,
+}
+
+impl Querier for FeeRefunderQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let min_fee = match (from_json(bin_request), &self.min_fee) {
+            (Ok(QueryRequest::<Empty>::Stargate { path, .. }), Some(min_fee))
+                if path == "/neutron.feerefunder.Query/Params" =>
+            {
+                min_fee.to_owned()
+            }
+            _ => return self.base.raw_query(bin_request),
+        };
+
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&FeeRefunderParamsResponse {
+                params: FeeRefunderParams { min_fee },
+            })
+            .unwrap(),
+        ))
+    }
+}
@@ -33,13 +33,21 @@ fn migrate_default() {
 fn default() -> StdResult<()> {
     let mut p = Project::new();
 
-    p.nft_minter_try_create_collection(ProjectAccount::Admin, "gopniks")?;
+    // wrapped collection is managed by the wrapper only
+    p.nft_minter_try_create_collection(ProjectAccount::Admin, "gopniks", p.get_wrapper_address())?;
 
     let collection_list = p.nft_minter_query_collection_list(9, None)?;
     let (collection_gopniks, _) = collection_list.first().unwrap();
 
     // register wrapper in nft-minter
-    p.nft_minter_try_update_config(ProjectAccount::Admin, &None, Some(&p.get_wrapper_address()))?;
+    p.nft_minter_try_update_config(
+        ProjectAccount::Admin,
+        &None,
+        Some(&p.get_wrapper_address()),
+        None,
+        None,
+        None,
+    )?;
 
     // register collection in wrapper
     p.wrapper_try_add_collection(